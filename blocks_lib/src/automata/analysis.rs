@@ -0,0 +1,127 @@
+use super::{BigUint, FiniteAutomata};
+
+impl FiniteAutomata {
+    /// True when no input is accepted.
+    pub fn is_empty(&self) -> bool {
+        !self
+            .reachable_indices()
+            .into_iter()
+            .any(|i| self.is_final_index(i))
+    }
+
+    /// True when every input over the alphabet is accepted.
+    pub fn is_universal(&self) -> bool {
+        self.complement().is_empty()
+    }
+
+    /// True when the language has finitely many inputs, that is when no cycle
+    /// goes through a state that is both reachable and able to accept.
+    pub fn is_finite(&self) -> bool {
+        let reachable = self.reachable_indices();
+        let coreachable = self.coreachable_indices();
+        let mut useful = vec![false; self.states.len()];
+        for i in reachable {
+            useful[i] = coreachable.contains(&i);
+        }
+
+        // depth first search, 1 marks the states on the current path
+        let mut color = vec![0u8; self.states.len()];
+        for start in 0..self.states.len() {
+            if !useful[start] || color[start] != 0 {
+                continue;
+            }
+            color[start] = 1;
+            let mut stack = vec![(start, 0)];
+            while let Some((i, ai)) = stack.pop() {
+                if ai == self.alphabets.len() {
+                    color[i] = 2;
+                    continue;
+                }
+                stack.push((i, ai + 1));
+                if let Some(j) = self.next_index(i, self.alphabets[ai]) {
+                    if !useful[j] {
+                        continue;
+                    }
+                    if color[j] == 1 {
+                        return false;
+                    }
+                    if color[j] == 0 {
+                        color[j] = 1;
+                        stack.push((j, 0));
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    /// Exact number of accepted inputs of length `len`. Counts the paths of
+    /// each length one step at a time, like powers of the transfer matrix.
+    pub fn count_accepted(&self, len: usize) -> BigUint {
+        let mut counts = vec![BigUint::zero(); self.states.len()];
+        match self.initial_index() {
+            Some(i) => counts[i] = BigUint::from(1),
+            None => return BigUint::zero(),
+        }
+        for _ in 0..len {
+            let mut next = vec![BigUint::zero(); self.states.len()];
+            for (i, count) in counts.iter().enumerate() {
+                if count.is_zero() {
+                    continue;
+                }
+                for a in &self.alphabets {
+                    if let Some(j) = self.next_index(i, *a) {
+                        next[j] = &next[j] + count;
+                    }
+                }
+            }
+            counts = next;
+        }
+        counts
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.is_final_index(*i))
+            .fold(BigUint::zero(), |total, (_, count)| &total + count)
+    }
+}
+
+#[cfg(test)]
+mod analysis_tests {
+    use crate::automata::fixtures::{from_table, last_one};
+
+    #[test]
+    fn basic() {
+        let last_one = last_one();
+        assert!(!last_one.is_empty());
+        assert!(!last_one.is_universal());
+        assert!(!last_one.is_finite());
+        assert!(last_one.union(&last_one.complement()).is_universal());
+        assert!(last_one.intersection(&last_one.complement()).is_empty());
+        assert_eq!("0", last_one.count_accepted(0).to_string());
+        assert_eq!("4", last_one.count_accepted(3).to_string());
+        assert_eq!(
+            "633825300114114700748351602688",
+            last_one.count_accepted(100).to_string()
+        );
+    }
+
+    #[test]
+    fn finite() {
+        // 0 or 01, with a trap that loops
+        let zero_one = from_table(
+            &['0', '1'],
+            &["B", "C"],
+            &[
+                ("A", '0', "B"),
+                ("A", '1', "D"),
+                ("B", '1', "C"),
+                ("D", '0', "D"),
+                ("D", '1', "D"),
+            ],
+        );
+        assert!(zero_one.is_finite());
+        assert_eq!("1", zero_one.count_accepted(1).to_string());
+        assert_eq!("1", zero_one.count_accepted(2).to_string());
+        assert_eq!("0", zero_one.count_accepted(3).to_string());
+    }
+}
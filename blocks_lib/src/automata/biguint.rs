@@ -0,0 +1,86 @@
+use std::{fmt, ops::Add};
+
+/// Base of the limbs, a power of ten so printing is just padding.
+const BASE: u64 = 1_000_000_000;
+
+/// Unsigned integer without an upper bound. Only what word counting needs is
+/// implemented: addition, comparison and decimal printing.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct BigUint {
+    // little endian limbs below `BASE`, without trailing zeros
+    limbs: Vec<u64>,
+}
+
+impl BigUint {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(mut n: u64) -> Self {
+        let mut limbs = Vec::new();
+        while n > 0 {
+            limbs.push(n % BASE);
+            n /= BASE;
+        }
+        Self { limbs }
+    }
+}
+
+impl Add<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let mut limbs = Vec::with_capacity(self.limbs.len().max(other.limbs.len()) + 1);
+        let mut carry = 0;
+        for i in 0..self.limbs.len().max(other.limbs.len()) {
+            let sum = self.limbs.get(i).unwrap_or(&0) + other.limbs.get(i).unwrap_or(&0) + carry;
+            limbs.push(sum % BASE);
+            carry = sum / BASE;
+        }
+        if carry > 0 {
+            limbs.push(carry);
+        }
+        BigUint { limbs }
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.limbs.split_last() {
+            None => write!(f, "0"),
+            Some((last, rest)) => {
+                write!(f, "{}", last)?;
+                for limb in rest.iter().rev() {
+                    write!(f, "{:09}", limb)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod biguint_tests {
+    use super::BigUint;
+
+    #[test]
+    fn basic() {
+        assert_eq!("0", BigUint::zero().to_string());
+        assert_eq!("1000000007", BigUint::from(1_000_000_007).to_string());
+        let mut n = BigUint::from(1);
+        for _ in 0..100 {
+            n = &n + &n;
+        }
+        assert_eq!("1267650600228229401496703205376", n.to_string());
+        assert_eq!(
+            BigUint::from(u64::MAX),
+            &BigUint::from(u64::MAX - 5) + &BigUint::from(5)
+        );
+    }
+}
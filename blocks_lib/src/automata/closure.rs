@@ -0,0 +1,192 @@
+use super::{FiniteAutomata, NondeterministicAutomata};
+
+impl FiniteAutomata {
+    /// Inputs made of an input of `self` followed by an input of `other`.
+    pub fn concat(&self, other: &FiniteAutomata) -> FiniteAutomata {
+        let offset = self.states.len();
+        let mut moves = self.moves(0);
+        moves.extend(other.moves(offset));
+        if let Some(j) = other.initial_index() {
+            for i in (0..offset).filter(|i| self.is_final_index(*i)) {
+                moves.push((i, None, offset + j));
+            }
+        }
+        let finals: Vec<usize> = (0..other.states.len())
+            .filter(|i| other.is_final_index(*i))
+            .map(|i| offset + i)
+            .collect();
+        let initials: Vec<usize> = self.initial_index().into_iter().collect();
+        let mut alphabets = self.alphabets.clone();
+        for a in &other.alphabets {
+            if !alphabets.contains(a) {
+                alphabets.push(*a);
+            }
+        }
+        determinized(
+            &alphabets,
+            offset + other.states.len(),
+            &initials,
+            &finals,
+            moves,
+            self.rejects_unknown && other.rejects_unknown,
+        )
+    }
+
+    /// Inputs made of any number of inputs of `self`, none included.
+    pub fn star(&self) -> FiniteAutomata {
+        // a new initial state, so that accepting the empty input does not
+        // make the old initial state final
+        let start = self.states.len();
+        let mut moves = self.loop_back();
+        moves.extend(self.initial_index().map(|i| (start, None, i)));
+        let finals: Vec<usize> = (0..=start)
+            .filter(|i| *i == start || self.is_final_index(*i))
+            .collect();
+        determinized(
+            &self.alphabets,
+            start + 1,
+            &[start],
+            &finals,
+            moves,
+            self.rejects_unknown,
+        )
+    }
+
+    /// Inputs made of one or more inputs of `self`.
+    pub fn plus(&self) -> FiniteAutomata {
+        let finals = self.final_indices();
+        let initials: Vec<usize> = self.initial_index().into_iter().collect();
+        let moves = self.loop_back();
+        determinized(
+            &self.alphabets,
+            self.states.len(),
+            &initials,
+            &finals,
+            moves,
+            self.rejects_unknown,
+        )
+    }
+
+    /// Inputs of `self` read backwards. The final states become the initial
+    /// ones and every transition is turned around.
+    pub fn reverse(&self) -> FiniteAutomata {
+        let moves = self
+            .moves(0)
+            .into_iter()
+            .map(|(i, a, j)| (j, a, i))
+            .collect();
+        let finals: Vec<usize> = self.initial_index().into_iter().collect();
+        let initials = self.final_indices();
+        determinized(
+            &self.alphabets,
+            self.states.len(),
+            &initials,
+            &finals,
+            moves,
+            self.rejects_unknown,
+        )
+    }
+
+    /// Transitions of `self` with the states shifted by `offset`.
+    fn moves(&self, offset: usize) -> Vec<(usize, Option<char>, usize)> {
+        let mut moves = Vec::new();
+        for i in 0..self.states.len() {
+            for a in &self.alphabets {
+                if let Some(j) = self.next_index(i, *a) {
+                    moves.push((offset + i, Some(*a), offset + j));
+                }
+            }
+        }
+        moves
+    }
+
+    /// Transitions of `self` plus ε-moves from the final states back to the
+    /// initial one.
+    fn loop_back(&self) -> Vec<(usize, Option<char>, usize)> {
+        let mut moves = self.moves(0);
+        if let Some(initial) = self.initial_index() {
+            for i in self.final_indices() {
+                moves.push((i, None, initial));
+            }
+        }
+        moves
+    }
+
+    fn final_indices(&self) -> Vec<usize> {
+        (0..self.states.len())
+            .filter(|i| self.is_final_index(*i))
+            .collect()
+    }
+}
+
+/// Determinizes the automata with `len` states, given by index, and numbers
+/// the resulting states. A `None` symbol is an ε-move, `rejects_unknown` is
+/// set as in [`FiniteAutomata::reject_unknown`].
+fn determinized(
+    alphabets: &[char],
+    len: usize,
+    initials: &[usize],
+    finals: &[usize],
+    moves: Vec<(usize, Option<char>, usize)>,
+    rejects_unknown: bool,
+) -> FiniteAutomata {
+    let states: Vec<String> = (0..len).map(|i| format!("q{}", i)).collect();
+    let mut nfa = NondeterministicAutomata::new();
+    nfa.add_alphabets(alphabets);
+    nfa.add_states(&states);
+    for i in initials {
+        nfa.initial_state(states[*i].clone());
+    }
+    for i in finals {
+        nfa.final_state(states[*i].clone());
+    }
+    for (state1, a, state2) in moves {
+        match a {
+            Some(a) => nfa.add_action(states[state1].clone(), &[a], &[states[state2].clone()]),
+            None => nfa.add_epsilon_action(states[state1].clone(), &[states[state2].clone()]),
+        }
+    }
+    let mut fa = nfa.determinize();
+    fa.reject_unknown(rejects_unknown);
+    fa.numbered()
+}
+
+#[cfg(test)]
+mod closure_tests {
+    use crate::automata::FiniteAutomata;
+
+    fn accepted(fa: &FiniteAutomata, count: usize) -> Vec<String> {
+        fa.accepted_words(usize::MAX).take(count).collect()
+    }
+
+    #[test]
+    fn concat() {
+        let a = FiniteAutomata::from_regex("a|ab").unwrap();
+        let b = FiniteAutomata::from_regex("b*c").unwrap();
+        let ab = a.concat(&b);
+        assert_eq!(['a', 'b', 'c'], ab.alphabets());
+        assert_eq!(vec!["ac", "abc", "abbc", "abbbc"], accepted(&ab, 4));
+        assert_eq!(Some(false), ab.exec("a".chars()));
+    }
+
+    #[test]
+    fn star() {
+        let ab = FiniteAutomata::from_regex("ab").unwrap();
+        assert_eq!(vec!["", "ab", "abab"], accepted(&ab.star(), 3));
+        assert_eq!(vec!["ab", "abab", "ababab"], accepted(&ab.plus(), 3));
+
+        // the initial state of `a*b` loops, so `b` must not accept the empty input
+        let loops = FiniteAutomata::from_regex("a*b").unwrap();
+        assert_eq!(Some(false), loops.plus().exec("a".chars()));
+        assert_eq!(Some(true), loops.star().exec("".chars()));
+        assert_eq!(Some(false), loops.star().exec("a".chars()));
+    }
+
+    #[test]
+    fn reverse() {
+        let fa = FiniteAutomata::from_regex("a+b|c").unwrap();
+        assert_eq!(vec!["c", "ba", "baa"], accepted(&fa.reverse(), 3));
+        assert!(fa.reverse().reverse().included_in(&fa).is_ok());
+        assert!(fa.included_in(&fa.reverse().reverse()).is_ok());
+    }
+}
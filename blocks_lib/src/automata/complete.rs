@@ -0,0 +1,78 @@
+use super::FiniteAutomata;
+
+/// Name given to the trap state, primed until it does not clash with a
+/// declared state.
+const TRAP_STATE: &str = "∅";
+
+impl FiniteAutomata {
+    /// Adds a trap state for every missing (state, symbol) pair. The trap
+    /// loops on every symbol and is never final, so the language is unchanged.
+    /// An automata that is already complete comes back as is.
+    pub fn complete(&self) -> FiniteAutomata {
+        let mut fa = self.clone();
+        if self.delta.iter().all(Option::is_some) {
+            return fa;
+        }
+        let mut states = self.states.clone();
+        states.push(self.trap_state());
+        fa.add_states(&states);
+        let trap = Some(self.states.len());
+        for next in fa.delta.iter_mut().filter(|next| next.is_none()) {
+            *next = trap;
+        }
+        fa
+    }
+
+    /// Name for a trap state added to `self`.
+    pub(super) fn trap_state(&self) -> String {
+        let mut trap = TRAP_STATE.to_string();
+        while self.state_index(&trap).is_some() {
+            trap.push('\'');
+        }
+        trap
+    }
+
+    /// Accepts exactly the strings over the alphabet that `self` rejects.
+    /// Strings with other symbols give `None` when run.
+    pub fn complement(&self) -> FiniteAutomata {
+        let mut fa = self.complete();
+        // the symbols outside the alphabet are left undefined, not accepted
+        fa.rejects_unknown = false;
+        for is_final in fa.finals.iter_mut() {
+            *is_final = !*is_final;
+        }
+        fa
+    }
+}
+
+#[cfg(test)]
+mod complete_tests {
+    use super::super::fixtures::from_table;
+    use super::FiniteAutomata;
+
+    // exactly 01
+    fn zero_one() -> FiniteAutomata {
+        from_table(&['0', '1'], &["C"], &[("A", '0', "B"), ("B", '1', "C")])
+    }
+
+    #[test]
+    fn complete() {
+        let fa = zero_one();
+        assert_eq!(None, fa.exec("1".chars()));
+        let complete = fa.complete();
+        assert_eq!(vec!["A", "B", "C", "∅"], complete.states);
+        assert_eq!(Some(false), complete.exec("1".chars()));
+        assert_eq!(Some(false), complete.exec("0110".chars()));
+        assert_eq!(Some(true), complete.exec("01".chars()));
+        assert_eq!(complete.states, complete.complete().states);
+    }
+
+    #[test]
+    fn complement() {
+        let complement = zero_one().complement();
+        for input in ["", "0", "1", "00", "011", "10"] {
+            assert_eq!(Some(true), complement.exec(input.chars()));
+        }
+        assert_eq!(Some(false), complement.exec("01".chars()));
+    }
+}
@@ -0,0 +1,188 @@
+use super::{
+    regex::{Named, Regex},
+    FiniteAutomata,
+};
+
+impl FiniteAutomata {
+    /// Regular expression of the language, in the syntax read by
+    /// [`FiniteAutomata::from_regex`], or `None` when nothing is accepted.
+    ///
+    /// Uses state elimination on the states that are reachable and can still
+    /// accept, always removing the state with the fewest paths through it. The
+    /// expressions are simplified while they are built.
+    pub fn to_regex(&self) -> Option<String> {
+        self.regex().map(|regex| regex.to_string())
+    }
+
+    /// Same as [`FiniteAutomata::to_regex`] with each symbol spelled by `name`.
+    /// When some symbol is spelled with several characters, the symbols are
+    /// words set apart by spaces and a class is a union, like
+    /// `login (read|write)*`, in the syntax read by
+    /// [`FiniteAutomata::from_regex_with`].
+    pub fn to_regex_with(&self, name: impl Fn(char) -> String) -> Option<String> {
+        if self.alphabets.iter().all(|a| name(*a).chars().count() == 1) {
+            return self.to_regex();
+        }
+        self.regex().map(|regex| Named(&regex, &name).to_string())
+    }
+
+    fn regex(&self) -> Option<Regex> {
+        let reachable = self.reachable_indices();
+        let coreachable = self.coreachable_indices();
+        let useful: Vec<usize> = reachable
+            .into_iter()
+            .filter(|i| coreachable.contains(i))
+            .collect();
+        let initial = self.initial_index()?;
+        if !useful.contains(&initial) {
+            return None;
+        }
+
+        // generalized automata over the useful states plus a new start and a
+        // new end, `edges[i][j]` being `None` when there is no edge
+        let n = useful.len();
+        let (start, end) = (n, n + 1);
+        let mut edges: Vec<Vec<Option<Regex>>> = vec![vec![None; n + 2]; n + 2];
+        for (i, state) in useful.iter().enumerate() {
+            for a in &self.alphabets {
+                let next = self.next_index(*state, *a);
+                if let Some(j) = next.and_then(|next| useful.iter().position(|s| *s == next)) {
+                    edges[i][j] = Some(union(edges[i][j].take(), Regex::Symbols(vec![*a])));
+                }
+            }
+            if self.is_final_index(*state) {
+                edges[i][end] = Some(Regex::Epsilon);
+            }
+        }
+        edges[start][useful.iter().position(|s| *s == initial).unwrap()] = Some(Regex::Epsilon);
+
+        let mut remaining: Vec<usize> = (0..n).collect();
+        while !remaining.is_empty() {
+            let paths = |k: usize| {
+                let ins = (0..n + 2)
+                    .filter(|i| *i != k && edges[*i][k].is_some())
+                    .count();
+                let outs = (0..n + 2)
+                    .filter(|j| *j != k && edges[k][*j].is_some())
+                    .count();
+                ins * outs
+            };
+            let position = (0..remaining.len())
+                .min_by_key(|p| paths(remaining[*p]))
+                .unwrap();
+            let k = remaining.remove(position);
+
+            let lp = edges[k][k].take().map_or(Regex::Epsilon, Regex::star);
+            let outs: Vec<(usize, Regex)> = edges[k]
+                .iter_mut()
+                .enumerate()
+                .filter_map(|(j, out)| out.take().map(|out| (j, out)))
+                .collect();
+            for row in edges.iter_mut() {
+                let into = match row[k].take() {
+                    Some(into) => into,
+                    None => continue,
+                };
+                for (j, out) in &outs {
+                    let path = into.clone().concat(lp.clone()).concat(out.clone());
+                    row[*j] = Some(union(row[*j].take(), path));
+                }
+            }
+        }
+        edges[start][end].take()
+    }
+}
+
+fn union(left: Option<Regex>, right: Regex) -> Regex {
+    match left {
+        Some(left) => left.union(right),
+        None => right,
+    }
+}
+
+#[cfg(test)]
+mod elimination_tests {
+    use crate::automata::{
+        equivalent,
+        fixtures::{from_table, last_one},
+        FiniteAutomata, SymbolTable,
+    };
+
+    #[test]
+    fn basic() {
+        let last_one = last_one();
+        assert_eq!(Some("0*1(1|0+1)*".to_string()), last_one.to_regex());
+
+        let zero_one = from_table(
+            &['0', '1'],
+            &["B", "C"],
+            &[("A", '0', "B"), ("B", '1', "C")],
+        );
+        assert_eq!(Some("01?".to_string()), zero_one.to_regex());
+
+        let empty = from_table(&['0', '1'], &[], &[("A", '0', "B")]);
+        assert_eq!(None, empty.to_regex());
+    }
+
+    #[test]
+    fn named() {
+        let session = from_table(
+            &['l', 'o', 'r', 'w'],
+            &["C"],
+            &[
+                ("A", 'l', "B"),
+                ("B", 'r', "B"),
+                ("B", 'w', "B"),
+                ("B", 'o', "C"),
+            ],
+        );
+        let name = |a| match a {
+            'l' => "login".to_string(),
+            'o' => "logout".to_string(),
+            'r' => "read".to_string(),
+            _ => a.to_string(),
+        };
+        assert_eq!(
+            Some("login (read|w)* logout".to_string()),
+            session.to_regex_with(name)
+        );
+        assert_eq!(
+            Some("l[rw]*o".to_string()),
+            session.to_regex_with(|a| a.to_string())
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for pattern in [
+            "(0|1)*1",
+            "a+b?[0-2]|()",
+            "(ab|ba)*c",
+            "[a-e]x*|\\(\\)",
+            "ε",
+        ] {
+            let fa = FiniteAutomata::from_regex(pattern).unwrap();
+            let regex = fa.to_regex().unwrap();
+            let back = FiniteAutomata::from_regex(&regex).unwrap();
+            assert_eq!(Ok(()), equivalent(&fa, &back), "{} -> {}", pattern, regex);
+        }
+    }
+
+    #[test]
+    fn round_trip_words() {
+        let mut symbols = SymbolTable::new();
+        for pattern in [
+            "login (read|w)* logout",
+            "(get|put)+ ε | a\\|b",
+            "\\ε \\  \\* and\\ or",
+        ] {
+            let fa = FiniteAutomata::from_regex_with(pattern, |t| symbols.symbol(t)).unwrap();
+            let regex = fa.to_regex_with(|a| symbols.token(a)).unwrap();
+            let back = FiniteAutomata::from_regex_with(&regex, |t| symbols.symbol(t)).unwrap();
+            assert_eq!(Ok(()), equivalent(&fa, &back), "{} -> {}", pattern, regex);
+        }
+        let fa = FiniteAutomata::from_regex_with("login (read|w)* logout", |t| symbols.symbol(t));
+        let regex = fa.unwrap().to_regex_with(|a| symbols.token(a));
+        assert_eq!(Some("login (w|read)* logout".to_string()), regex);
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::{HashMap, VecDeque};
+
+use super::FiniteAutomata;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+/// Shortest input two automata disagree on, with the side that accepts it.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Counterexample {
+    pub input: String,
+    pub accepted_by: Side,
+}
+
+/// Checks that `left` and `right` accept the same language with Hopcroft and
+/// Karp's union-find algorithm. Missing transitions lead to a rejecting trap,
+/// and the alphabets are unified first. When the languages differ the
+/// shortest (then smallest) input they disagree on is returned.
+pub fn equivalent(left: &FiniteAutomata, right: &FiniteAutomata) -> Result<(), Counterexample> {
+    let alphabets = merged_alphabets(left, right);
+    // left states come first, right states are shifted by `offset`, and each
+    // side has one extra id for its trap
+    let offset = left.states.len() + 1;
+    let id = |state: Option<usize>, trap: usize, shift: usize| state.unwrap_or(trap) + shift;
+    let left_trap = left.states.len();
+    let right_trap = right.states.len();
+
+    let mut parent: Vec<usize> = (0..offset + right_trap + 1).collect();
+    let mut stack = vec![(left.initial_index(), right.initial_index())];
+    union(
+        &mut parent,
+        id(left.initial_index(), left_trap, 0),
+        id(right.initial_index(), right_trap, offset),
+    );
+    while let Some((p, q)) = stack.pop() {
+        let p_final = p.is_some_and(|p| left.is_final_index(p));
+        let q_final = q.is_some_and(|q| right.is_final_index(q));
+        if p_final != q_final {
+            let (input, accepted_by_left) =
+                shortest_input(left, right, &alphabets, |p, q| p != q).unwrap();
+            return Err(Counterexample {
+                input,
+                accepted_by: if accepted_by_left {
+                    Side::Left
+                } else {
+                    Side::Right
+                },
+            });
+        }
+        for a in &alphabets {
+            let p = p.and_then(|p| left.next_index(p, *a));
+            let q = q.and_then(|q| right.next_index(q, *a));
+            if union(&mut parent, id(p, left_trap, 0), id(q, right_trap, offset)) {
+                stack.push((p, q));
+            }
+        }
+    }
+    Ok(())
+}
+
+impl FiniteAutomata {
+    /// Checks that every input accepted by `self` is also accepted by `other`.
+    /// Otherwise the shortest (then smallest) input accepted only by `self` is
+    /// returned.
+    pub fn included_in(&self, other: &FiniteAutomata) -> Result<(), String> {
+        let alphabets = merged_alphabets(self, other);
+        match shortest_input(self, other, &alphabets, |p, q| p && !q) {
+            Some((input, _)) => Err(input),
+            None => Ok(()),
+        }
+    }
+}
+
+fn merged_alphabets(left: &FiniteAutomata, right: &FiniteAutomata) -> Vec<char> {
+    let mut alphabets: Vec<char> = left
+        .alphabets
+        .iter()
+        .chain(&right.alphabets)
+        .copied()
+        .collect();
+    alphabets.sort();
+    alphabets.dedup();
+    alphabets
+}
+
+fn find(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+/// Merges the classes of `i` and `j`, returns false when they were already one.
+fn union(parent: &mut [usize], i: usize, j: usize) -> bool {
+    let i = find(parent, i);
+    let j = find(parent, j);
+    if i == j {
+        return false;
+    }
+    parent[j] = i;
+    true
+}
+
+/// Breadth first search over the pairs of states, symbols in alphabetical
+/// order, for the first pair where `found` holds on the finality of both
+/// sides. Returns the input leading there and whether the left side accepts.
+fn shortest_input(
+    left: &FiniteAutomata,
+    right: &FiniteAutomata,
+    alphabets: &[char],
+    found: impl Fn(bool, bool) -> bool,
+) -> Option<(String, bool)> {
+    type Pair = (Option<usize>, Option<usize>);
+    let start: Pair = (left.initial_index(), right.initial_index());
+    // pair each one was first reached from, with the symbol read
+    let mut parents: HashMap<Pair, Option<(Pair, char)>> = HashMap::from([(start, None)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(pair) = queue.pop_front() {
+        let (p, q) = pair;
+        let p_final = p.is_some_and(|p| left.is_final_index(p));
+        let q_final = q.is_some_and(|q| right.is_final_index(q));
+        if found(p_final, q_final) {
+            let mut input = Vec::new();
+            let mut curr = pair;
+            while let Some((prev, a)) = parents[&curr] {
+                input.push(a);
+                curr = prev;
+            }
+            return Some((input.into_iter().rev().collect(), p_final));
+        }
+        for a in alphabets {
+            let next = (
+                p.and_then(|p| left.next_index(p, *a)),
+                q.and_then(|q| right.next_index(q, *a)),
+            );
+            if next != (None, None) && !parents.contains_key(&next) {
+                parents.insert(next, Some((pair, *a)));
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod equivalence_tests {
+    use super::{equivalent, Counterexample, Side};
+    use crate::automata::fixtures::{from_table, last_one};
+
+    #[test]
+    fn basic() {
+        let last_one = last_one();
+        // the same language with redundant states
+        let redundant = from_table(
+            &['0', '1'],
+            &["B", "D"],
+            &[
+                ("A", '0', "C"),
+                ("A", '1', "B"),
+                ("B", '0', "C"),
+                ("B", '1', "D"),
+                ("C", '0', "A"),
+                ("C", '1', "D"),
+                ("D", '0', "A"),
+                ("D", '1', "B"),
+            ],
+        );
+        assert_eq!(Ok(()), equivalent(&last_one, &redundant));
+        assert_eq!(Ok(()), equivalent(&last_one, &last_one.minimize()));
+    }
+
+    #[test]
+    fn counterexample() {
+        let last_one = last_one();
+        // ends in 11
+        let two_ones = from_table(
+            &['0', '1'],
+            &["C"],
+            &[
+                ("A", '0', "A"),
+                ("A", '1', "B"),
+                ("B", '0', "A"),
+                ("B", '1', "C"),
+                ("C", '0', "A"),
+                ("C", '1', "C"),
+            ],
+        );
+        let expected = Counterexample {
+            input: "1".to_string(),
+            accepted_by: Side::Left,
+        };
+        assert_eq!(Err(expected), equivalent(&last_one, &two_ones));
+        // not ending in 1 or ending in 11, against not ending in 11
+        let not_single_one = last_one.complement().union(&two_ones);
+        let expected = Counterexample {
+            input: "1".to_string(),
+            accepted_by: Side::Right,
+        };
+        assert_eq!(
+            Err(expected),
+            equivalent(&not_single_one, &two_ones.complement())
+        );
+    }
+
+    #[test]
+    fn included_in() {
+        let last_one = last_one();
+        // exactly 01, with missing transitions
+        let zero_one = from_table(&['0', '1'], &["C"], &[("A", '0', "B"), ("B", '1', "C")]);
+        assert_eq!(Ok(()), zero_one.included_in(&last_one));
+        assert_eq!(Err("1".to_string()), last_one.included_in(&zero_one));
+        assert_eq!(Ok(()), last_one.included_in(&last_one.minimize()));
+    }
+}
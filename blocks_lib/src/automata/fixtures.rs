@@ -0,0 +1,88 @@
+use super::{FiniteAutomata, NondeterministicAutomata};
+
+/// Builds an automata from a transition table for tests. The states are
+/// declared in order of appearance and the first one is initial.
+pub(super) fn from_table(
+    alphas: &[char],
+    finals: &[&str],
+    actions: &[(&str, char, &str)],
+) -> FiniteAutomata {
+    let states = table_states(actions);
+    let mut fa = FiniteAutomata::new();
+    fa.add_alphabets(alphas);
+    fa.add_states(&states);
+    fa.initial_state(states[0].clone());
+    for f in finals {
+        fa.final_state(f.to_string());
+    }
+    for (s1, a, s2) in actions {
+        fa.add_action(s1.to_string(), &[*a], s2.to_string());
+    }
+    fa
+}
+
+/// Same as [`from_table`] for a nondeterministic automata, `ε` standing for
+/// the ε-moves.
+pub(super) fn nfa_from_table(
+    alphas: &[char],
+    finals: &[&str],
+    actions: &[(&str, char, &str)],
+) -> NondeterministicAutomata {
+    let states = table_states(actions);
+    let mut nfa = NondeterministicAutomata::new();
+    nfa.add_alphabets(alphas);
+    nfa.add_states(&states);
+    nfa.initial_state(states[0].clone());
+    for f in finals {
+        nfa.final_state(f.to_string());
+    }
+    for (s1, a, s2) in actions {
+        if *a == 'ε' {
+            nfa.add_epsilon_action(s1.to_string(), &[s2.to_string()]);
+        } else {
+            nfa.add_action(s1.to_string(), &[*a], &[s2.to_string()]);
+        }
+    }
+    nfa
+}
+
+/// Accepts the inputs ending in 1.
+pub(super) fn last_one() -> FiniteAutomata {
+    from_table(
+        &['0', '1'],
+        &["B"],
+        &[
+            ("A", '0', "A"),
+            ("A", '1', "B"),
+            ("B", '0', "A"),
+            ("B", '1', "B"),
+        ],
+    )
+}
+
+/// D is unreachable and C can never accept.
+pub(super) fn useless_states() -> FiniteAutomata {
+    from_table(
+        &['0', '1'],
+        &["B"],
+        &[
+            ("A", '0', "B"),
+            ("A", '1', "C"),
+            ("B", '0', "B"),
+            ("C", '0', "C"),
+            ("D", '0', "B"),
+        ],
+    )
+}
+
+fn table_states(actions: &[(&str, char, &str)]) -> Vec<String> {
+    let mut states: Vec<String> = Vec::new();
+    for (s1, _, s2) in actions {
+        for s in [s1, s2] {
+            if !states.iter().any(|st| st == s) {
+                states.push(s.to_string());
+            }
+        }
+    }
+    states
+}
@@ -0,0 +1,183 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    str::Chars,
+};
+
+use super::MooreMachine;
+
+/// Transducer writing an output on every transition. There are no final
+/// states, running it turns an input into the concatenation of the outputs
+/// of the transitions taken.
+#[derive(Default, Debug, Clone)]
+pub struct MealyMachine {
+    alphabets: Vec<char>,
+    states: Vec<String>,
+    initial_state: String,
+    delta: HashMap<(String, char), (String, String)>,
+}
+
+impl MealyMachine {
+    pub fn new() -> Self {
+        Self {
+            ..Default::default()
+        }
+    }
+
+    pub fn add_alphabets(&mut self, alpha: &[char]) {
+        self.alphabets = Vec::from(alpha);
+    }
+
+    pub fn add_states(&mut self, states: &[String]) {
+        self.states = Vec::from(states);
+    }
+
+    pub fn initial_state(&mut self, state: String) {
+        self.initial_state = state;
+    }
+
+    /// Moves from `state1` to `state2` on every symbol of `alpha`, writing
+    /// `output`. Undeclared states are ignored.
+    pub fn add_action(&mut self, state1: String, alpha: &[char], output: String, state2: String) {
+        if !self.states.contains(&state1) || !self.states.contains(&state2) {
+            return;
+        }
+        for a in alpha {
+            self.delta
+                .insert((state1.clone(), *a), (state2.clone(), output.clone()));
+        }
+    }
+
+    pub fn add_actions(&mut self, actions: Vec<(String, &[char], String, String)>) {
+        for action in actions {
+            let (state1, alpha, output, state2) = action;
+            self.add_action(state1, alpha, output, state2);
+        }
+    }
+
+    /// Output written while reading `input`, `None` when some symbol has no
+    /// transition.
+    pub fn exec(&self, input: Chars) -> Option<String> {
+        let mut curr_state = &self.initial_state;
+        let mut output = String::new();
+        for a in input {
+            let (next, out) = self.delta.get(&(curr_state.clone(), a))?;
+            output.push_str(out);
+            curr_state = next;
+        }
+        Some(output)
+    }
+
+    pub fn states(&self) -> &[String] {
+        &self.states
+    }
+
+    pub fn alphabets(&self) -> &[char] {
+        &self.alphabets
+    }
+
+    /// Equivalent Moore machine. A state is split by the output written when
+    /// entering it, so the states are named like `(A,x)`, the initial one
+    /// being `(A,ε)` with an empty label. Only the reachable pairs are kept.
+    pub fn to_moore(&self) -> MooreMachine {
+        let name = |(state, output): &(String, String)| {
+            let output = if output.is_empty() { "ε" } else { output };
+            format!("({},{})", state, output)
+        };
+
+        let mut pairs = vec![(self.initial_state.clone(), String::new())];
+        let mut queue = VecDeque::from([0]);
+        let mut actions = Vec::new();
+        while let Some(i) = queue.pop_front() {
+            for a in &self.alphabets {
+                let next = match self.delta.get(&(pairs[i].0.clone(), *a)) {
+                    Some(next) => next.clone(),
+                    None => continue,
+                };
+                let j = match pairs.iter().position(|p| *p == next) {
+                    Some(j) => j,
+                    None => {
+                        pairs.push(next);
+                        queue.push_back(pairs.len() - 1);
+                        pairs.len() - 1
+                    }
+                };
+                actions.push((i, *a, j));
+            }
+        }
+
+        let names: Vec<String> = pairs.iter().map(name).collect();
+        let mut moore = MooreMachine::new();
+        moore.add_alphabets(&self.alphabets);
+        moore.add_states(&names);
+        moore.initial_state(names[0].clone());
+        for ((_, output), name) in pairs.iter().zip(&names) {
+            moore.state_output(name.clone(), output.clone());
+        }
+        for (i, a, j) in actions {
+            moore.add_action(names[i].clone(), &[a], names[j].clone());
+        }
+        moore
+    }
+}
+
+#[cfg(test)]
+mod mealy_tests {
+    use super::MealyMachine;
+
+    // writes 1 when the symbol differs from the previous one, like an NRZI
+    // encoder
+    fn changes() -> MealyMachine {
+        let alphas = ['0', '1'];
+        let states = vec!["Z".to_owned(), "O".to_owned()];
+        let mut mealy = MealyMachine::new();
+        mealy.add_alphabets(&alphas);
+        mealy.add_states(&states);
+        mealy.initial_state(states[0].clone());
+        mealy.add_actions(vec![
+            (
+                states[0].clone(),
+                &alphas[0..1],
+                "0".to_owned(),
+                states[0].clone(),
+            ),
+            (
+                states[0].clone(),
+                &alphas[1..2],
+                "1".to_owned(),
+                states[1].clone(),
+            ),
+            (
+                states[1].clone(),
+                &alphas[0..1],
+                "1".to_owned(),
+                states[0].clone(),
+            ),
+            (
+                states[1].clone(),
+                &alphas[1..2],
+                "0".to_owned(),
+                states[1].clone(),
+            ),
+        ]);
+        mealy
+    }
+
+    #[test]
+    fn basic() {
+        let mealy = changes();
+        assert_eq!(Some("".to_owned()), mealy.exec("".chars()));
+        assert_eq!(Some("0110".to_owned()), mealy.exec("0100".chars()));
+        assert_eq!(Some("1000".to_owned()), mealy.exec("1111".chars()));
+        assert_eq!(None, mealy.exec("012".chars()));
+    }
+
+    #[test]
+    fn to_moore() {
+        let moore = changes().to_moore();
+        assert_eq!(
+            vec!["(Z,ε)", "(Z,0)", "(O,1)", "(Z,1)", "(O,0)"],
+            moore.states()
+        );
+        assert_eq!(Some("0110".to_owned()), moore.exec("0100".chars()));
+    }
+}
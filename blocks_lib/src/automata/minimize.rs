@@ -0,0 +1,314 @@
+use std::collections::HashSet;
+
+use super::FiniteAutomata;
+
+impl FiniteAutomata {
+    pub fn minimize(&self) -> FiniteAutomata {
+        self.minimize_with_report().0
+    }
+
+    /// Removes the unreachable states and merges the equivalent ones with
+    /// Hopcroft's partition refinement. Every merged state keeps the name of
+    /// its first declared member, and the report lists the original states
+    /// behind each state of the minimal automata. Missing transitions go to a
+    /// trap state, so the result is complete.
+    pub fn minimize_with_report(&self) -> (FiniteAutomata, Vec<(String, Vec<String>)>) {
+        let initial = match self.initial_index() {
+            Some(initial) => initial,
+            None => return (self.clone(), Vec::new()),
+        };
+        let reachable = self.reachable_indices();
+        let n = reachable.len();
+        let k = self.alphabets.len();
+
+        // local ids for the reachable states, `n` being an implicit trap state
+        // for the missing transitions
+        let dead = n;
+        let mut local = vec![dead; self.states.len()];
+        for (l, g) in reachable.iter().enumerate() {
+            local[*g] = l;
+        }
+        let mut table = vec![vec![dead; k]; n + 1];
+        for (l, g) in reachable.iter().enumerate() {
+            for (ai, a) in self.alphabets.iter().enumerate() {
+                if let Some(j) = self.next_index(*g, *a) {
+                    table[l][ai] = local[j];
+                }
+            }
+        }
+        let is_dead_used = table[..n].iter().flatten().any(|q| *q == dead);
+        let mut inverse = vec![vec![Vec::new(); k]; n + 1];
+        for (q, row) in table.iter().enumerate() {
+            for (ai, p) in row.iter().enumerate() {
+                inverse[*p][ai].push(q);
+            }
+        }
+
+        let (finals, non_finals): (Vec<usize>, Vec<usize>) =
+            (0..=n).partition(|q| *q != dead && self.is_final_index(reachable[*q]));
+        let mut partition = Partition::new(&[finals, non_finals]);
+
+        let mut worklist = Vec::new();
+        let mut in_worklist = HashSet::new();
+        if partition.len() == 2 {
+            let smaller = if partition.size(0) <= partition.size(1) {
+                0
+            } else {
+                1
+            };
+            for ai in 0..k {
+                worklist.push((smaller, ai));
+                in_worklist.insert((smaller, ai));
+            }
+        }
+        while let Some((b, ai)) = worklist.pop() {
+            in_worklist.remove(&(b, ai));
+            // only the predecessors of the splitter are visited
+            let predecessors: Vec<usize> = partition
+                .members(b)
+                .iter()
+                .flat_map(|q| inverse[*q][ai].iter().copied())
+                .collect();
+            for (y, z) in partition.split(&predecessors) {
+                for aj in 0..k {
+                    let next = if in_worklist.contains(&(y, aj))
+                        || partition.size(z) < partition.size(y)
+                    {
+                        z
+                    } else {
+                        y
+                    };
+                    if in_worklist.insert((next, aj)) {
+                        worklist.push((next, aj));
+                    }
+                }
+            }
+        }
+
+        // the trap block is dropped when it is only the unused trap state
+        let initial_block = partition.block_of[local[initial]];
+        let dead_block = partition.block_of[dead];
+        let mut blocks: Vec<Vec<usize>> = (0..partition.len())
+            .map(|b| {
+                let mut members = partition.members(b).to_vec();
+                members.sort();
+                members
+            })
+            .collect();
+        let mut kept: Vec<usize> = (0..blocks.len())
+            .filter(|b| is_dead_used || *b != dead_block || blocks[*b].len() > 1)
+            .collect();
+        for b in &mut blocks {
+            b.retain(|q| *q != dead);
+        }
+        kept.sort_by_key(|b| blocks[*b].first().copied().unwrap_or(dead));
+
+        let names: Vec<String> = kept
+            .iter()
+            .map(|b| match blocks[*b].first() {
+                Some(q) => self.states[reachable[*q]].clone(),
+                None => self.trap_state(),
+            })
+            .collect();
+        let mut fa = FiniteAutomata::new();
+        fa.rejects_unknown = self.rejects_unknown;
+        fa.add_alphabets(&self.alphabets);
+        fa.add_states(&names);
+        let mut report = Vec::with_capacity(kept.len());
+        for (b, name) in kept.iter().zip(&names) {
+            let members = &blocks[*b];
+            let representative = members.first().copied().unwrap_or(dead);
+            if *b == initial_block {
+                fa.initial_state(name.clone());
+            }
+            if representative != dead && self.is_final_index(reachable[representative]) {
+                fa.final_state(name.clone());
+            }
+            for (ai, a) in self.alphabets.iter().enumerate() {
+                let target = partition.block_of[table[representative][ai]];
+                if let Some(t) = kept.iter().position(|b| *b == target) {
+                    fa.add_action(name.clone(), &[*a], names[t].clone());
+                }
+            }
+            let originals = members
+                .iter()
+                .map(|q| self.states[reachable[*q]].clone())
+                .collect();
+            report.push((name.clone(), originals));
+        }
+        (fa, report)
+    }
+}
+
+/// Blocks of states kept as ranges of a single permutation, so a block is
+/// split in time proportional to the states marked in it.
+struct Partition {
+    elements: Vec<usize>,
+    position: Vec<usize>,
+    block_of: Vec<usize>,
+    // first element and end of each block
+    bounds: Vec<(usize, usize)>,
+    // number of elements marked at the start of each block
+    marked: Vec<usize>,
+}
+
+impl Partition {
+    fn new(parts: &[Vec<usize>]) -> Self {
+        let size = parts.iter().map(Vec::len).sum();
+        let mut partition = Partition {
+            elements: Vec::with_capacity(size),
+            position: vec![0; size],
+            block_of: vec![0; size],
+            bounds: Vec::new(),
+            marked: Vec::new(),
+        };
+        for part in parts.iter().filter(|part| !part.is_empty()) {
+            let start = partition.elements.len();
+            for q in part {
+                partition.position[*q] = partition.elements.len();
+                partition.block_of[*q] = partition.bounds.len();
+                partition.elements.push(*q);
+            }
+            partition.bounds.push((start, partition.elements.len()));
+            partition.marked.push(0);
+        }
+        partition
+    }
+
+    fn len(&self) -> usize {
+        self.bounds.len()
+    }
+
+    fn size(&self, b: usize) -> usize {
+        self.bounds[b].1 - self.bounds[b].0
+    }
+
+    fn members(&self, b: usize) -> &[usize] {
+        &self.elements[self.bounds[b].0..self.bounds[b].1]
+    }
+
+    /// Splits every block holding some but not all of `states`, the smaller
+    /// half becoming a new block. Returns the pairs of old and new blocks.
+    fn split(&mut self, states: &[usize]) -> Vec<(usize, usize)> {
+        let mut touched = Vec::new();
+        for q in states {
+            let b = self.block_of[*q];
+            let (start, _) = self.bounds[b];
+            let marked_end = start + self.marked[b];
+            if self.position[*q] < marked_end {
+                continue;
+            }
+            if self.marked[b] == 0 {
+                touched.push(b);
+            }
+            // swap `q` to the end of the marked states of its block
+            let other = self.elements[marked_end];
+            self.elements.swap(self.position[*q], marked_end);
+            self.position[other] = self.position[*q];
+            self.position[*q] = marked_end;
+            self.marked[b] += 1;
+        }
+
+        let mut splits = Vec::new();
+        for y in touched {
+            let (start, end) = self.bounds[y];
+            let middle = start + std::mem::take(&mut self.marked[y]);
+            if middle == end {
+                continue;
+            }
+            let z = self.bounds.len();
+            let (moved, kept) = if middle - start <= end - middle {
+                ((start, middle), (middle, end))
+            } else {
+                ((middle, end), (start, middle))
+            };
+            for q in &self.elements[moved.0..moved.1] {
+                self.block_of[*q] = z;
+            }
+            self.bounds[y] = kept;
+            self.bounds.push(moved);
+            self.marked.push(0);
+            splits.push((y, z));
+        }
+        splits
+    }
+}
+
+#[cfg(test)]
+mod minimize_tests {
+    use super::super::fixtures::from_table;
+    use super::FiniteAutomata;
+
+    // ends in 1, with a redundant copy of every state and an unreachable one
+    fn redundant_last_one() -> FiniteAutomata {
+        from_table(
+            &['0', '1'],
+            &["B", "D"],
+            &[
+                ("A", '1', "B"),
+                ("A", '0', "C"),
+                ("B", '0', "C"),
+                ("B", '1', "D"),
+                ("C", '0', "A"),
+                ("C", '1', "D"),
+                ("D", '0', "A"),
+                ("D", '1', "B"),
+                ("E", '0', "A"),
+                ("E", '1', "A"),
+            ],
+        )
+    }
+
+    #[test]
+    fn basic() {
+        let fa = redundant_last_one();
+        let (min, report) = fa.minimize_with_report();
+        assert_eq!(vec!["A", "B"], min.states);
+        assert_eq!(
+            vec![
+                ("A".to_string(), vec!["A".to_string(), "C".to_string()]),
+                ("B".to_string(), vec!["B".to_string(), "D".to_string()]),
+            ],
+            report
+        );
+        for input in ["", "0", "1", "10", "1101", "0110", "111"] {
+            assert_eq!(fa.exec(input.chars()), min.exec(input.chars()));
+        }
+    }
+
+    #[test]
+    fn partial() {
+        // 01 only, with missing transitions and a state that never accepts
+        let fa = from_table(
+            &['0', '1'],
+            &["C"],
+            &[
+                ("A", '0', "B"),
+                ("B", '1', "C"),
+                ("A", '1', "D"),
+                ("D", '0', "D"),
+                ("D", '1', "D"),
+            ],
+        );
+        // D takes the place of the trap state
+        let min = fa.minimize();
+        assert_eq!(vec!["A", "B", "C", "D"], min.states);
+        assert_eq!(Some(true), min.exec("01".chars()));
+        assert_eq!(Some(false), min.exec("1".chars()));
+        assert_eq!(Some(false), min.exec("00".chars()));
+    }
+
+    #[test]
+    fn trap() {
+        // exactly 01, the trap state is added
+        let fa = from_table(&['0', '1'], &["C"], &[("A", '0', "B"), ("B", '1', "C")]);
+        let (min, report) = fa.minimize_with_report();
+        assert_eq!(vec!["A", "B", "C", "∅"], min.states);
+        assert_eq!(("∅".to_string(), Vec::new()), report[3]);
+        for input in ["", "0", "1", "011", "10"] {
+            assert_eq!(Some(false), min.exec(input.chars()));
+        }
+        assert_eq!(Some(true), min.exec("01".chars()));
+        assert_eq!(min.states, min.minimize().states);
+    }
+}
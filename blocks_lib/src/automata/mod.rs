@@ -1,12 +1,68 @@
+mod analysis;
+mod biguint;
+mod closure;
+mod complete;
+mod elimination;
+mod equivalence;
+/// Automata built from transition tables, for the tests.
+#[cfg(test)]
+mod fixtures;
+mod mealy;
+mod minimize;
+mod moore;
+mod nfa;
+mod pda;
+mod product;
+mod regex;
+mod runner;
+mod symbols;
+mod tm;
+mod trace;
+mod trim;
+mod validate;
+mod weighted;
+mod words;
+
+pub use biguint::BigUint;
+pub use equivalence::{equivalent, Counterexample, Side};
+pub use mealy::MealyMachine;
+pub use moore::MooreMachine;
+pub use nfa::NondeterministicAutomata;
+pub use pda::{Acceptance, PushdownAutomata};
+pub use runner::Runner;
+pub use symbols::{symbol_class, SymbolTable};
+pub use tm::{Configuration, Halt, Move, TuringMachine, TuringRun};
+pub use trace::{Outcome, Step};
+pub use validate::Issue;
+pub use weighted::{Counting, Probability, Semiring, Tropical, WeightedAutomata};
+pub use words::Words;
+
+/// Steps a PDA search or a Turing machine run may take when no limit was set.
+pub const DEFAULT_STEP_LIMIT: usize = 10_000;
+
 use std::{collections::HashMap, str::Chars};
 
-#[derive(Default, Debug)]
+/// Deterministic finite automata. States and symbols are referred to by name
+/// when building it, but are stored as their index in `states` and
+/// `alphabets`, so running it only walks a table. The table is dense, with a
+/// column per symbol, and a class like `a-z` is declared as each of its
+/// symbols. Symbols are found by a binary search over the runs of
+/// consecutive ones.
+#[derive(Default, Debug, Clone)]
 pub struct FiniteAutomata {
     alphabets: Vec<char>,
     states: Vec<String>,
-    initial_state: String,
-    final_state: String,
-    delta: HashMap<(String, char), String>,
+    // runs of consecutive symbols with consecutive ids, as the first and last
+    // symbol and the id of the first, sorted
+    symbol_ranges: Vec<(char, char, usize)>,
+    state_ids: HashMap<String, usize>,
+    initial: Option<usize>,
+    finals: Vec<bool>,
+    // next state of state `i` on symbol `a` at `i * alphabets.len() + a`
+    delta: Vec<Option<usize>>,
+    // whether `exec` rejects the symbols outside the alphabet rather than
+    // giving `None`
+    rejects_unknown: bool,
 }
 
 impl FiniteAutomata {
@@ -17,34 +73,57 @@ impl FiniteAutomata {
     }
 
     pub fn add_alphabets(&mut self, alpha: &[char]) {
-        self.alphabets = Vec::from(alpha);
+        self.declare(Vec::from(alpha), self.states.clone());
     }
 
     pub fn add_states(&mut self, states: &[String]) {
-        self.states = Vec::from(states);
+        self.declare(self.alphabets.clone(), Vec::from(states));
     }
 
+    /// Undeclared states are ignored, like in [`FiniteAutomata::add_action`].
     pub fn initial_state(&mut self, state: String) {
-        self.initial_state = state;
+        self.initial = self.state_index(&state);
     }
 
     pub fn final_state(&mut self, state: String) {
-        self.final_state = state;
+        if let Some(i) = self.state_index(&state) {
+            self.finals[i] = true;
+        }
     }
 
+    pub fn final_states(&mut self, states: &[String]) {
+        self.finals = vec![false; self.states.len()];
+        for state in states {
+            self.final_state(state.clone());
+        }
+    }
+
+    /// Moves from `state1` to `state2` on every symbol of `alpha`. Undeclared
+    /// states or symbols are ignored.
     pub fn add_action(&mut self, state1: String, alpha: &[char], state2: String) {
+        let (i, j) = match (self.state_index(&state1), self.state_index(&state2)) {
+            (Some(i), Some(j)) => (i, j),
+            _ => return,
+        };
         for a in alpha {
-            let state1 = self.states.iter().position(|s| *s == state1);
-            let state2 = self.states.iter().position(|s| *s == state2);
-            if state1.is_none() && state2.is_none() {
-                return;
+            if let Some(a) = self.symbol_index(*a) {
+                let row = i * self.alphabets.len();
+                self.delta[row + a] = Some(j);
             }
-            let state1 = self.states[state1.unwrap()].clone();
-            let state2 = self.states[state2.unwrap()].clone();
-            self.delta.insert((state1, *a), state2);
         }
     }
 
+    /// Makes [`FiniteAutomata::exec`] reject the inputs with a symbol outside
+    /// the alphabet rather than give `None`, as automata compiled from a
+    /// regular expression do.
+    pub fn reject_unknown(&mut self, rejects: bool) {
+        self.rejects_unknown = rejects;
+    }
+
+    pub fn rejects_unknown(&self) -> bool {
+        self.rejects_unknown
+    }
+
     pub fn add_actions(&mut self, actions: Vec<(String, &[char], String)>) {
         for action in actions {
             let (state1, alpha, state2) = action;
@@ -52,19 +131,180 @@ impl FiniteAutomata {
         }
     }
 
+    /// Whether `input` is accepted, `None` when it gets stuck on a missing
+    /// transition or, unless [`FiniteAutomata::reject_unknown`] was set, on a
+    /// symbol outside the alphabet. Each char is a symbol, a
+    /// multi-character symbol being the char a [`SymbolTable`] gave it when
+    /// the automata was built, see [`SymbolTable::input`].
     pub fn exec(&self, input: Chars) -> Option<bool> {
-        let mut curr_state = self.initial_state.clone();
+        let mut state = self.initial;
         for a in input {
-            let cs = self.delta.get(&(curr_state, a));
-            if cs.is_none() {
-                return None;
+            if self.rejects_unknown && self.symbol_index(a).is_none() {
+                return Some(false);
+            }
+            state = Some(self.next_index(state?, a)?);
+        }
+        Some(state.is_some_and(|i| self.finals[i]))
+    }
+
+    pub fn states(&self) -> &[String] {
+        &self.states
+    }
+
+    pub fn alphabets(&self) -> &[char] {
+        &self.alphabets
+    }
+
+    /// Replaces the states and symbols, keeping what was said about the ones
+    /// still declared.
+    fn declare(&mut self, alphabets: Vec<char>, states: Vec<String>) {
+        let old = std::mem::take(self);
+        self.rejects_unknown = old.rejects_unknown;
+        self.symbol_ranges = symbol_ranges(&alphabets);
+        self.state_ids = states
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (s.clone(), i))
+            .collect();
+        self.finals = vec![false; states.len()];
+        self.delta = vec![None; states.len() * alphabets.len()];
+        self.alphabets = alphabets;
+        self.states = states;
+
+        self.initial = old.initial.and_then(|i| self.state_index(&old.states[i]));
+        for (i, state) in old.states.iter().enumerate() {
+            let k = match self.state_index(state) {
+                Some(k) => k,
+                None => continue,
+            };
+            self.finals[k] = old.finals[i];
+            for (ai, a) in old.alphabets.iter().enumerate() {
+                let next = old.delta[i * old.alphabets.len() + ai];
+                let next = next.and_then(|j| self.state_index(&old.states[j]));
+                if let (Some(b), Some(next)) = (self.symbol_index(*a), next) {
+                    let row = k * self.alphabets.len();
+                    self.delta[row + b] = Some(next);
+                }
+            }
+        }
+    }
+
+    fn state_index(&self, state: &str) -> Option<usize> {
+        self.state_ids.get(state).copied()
+    }
+
+    fn symbol_index(&self, a: char) -> Option<usize> {
+        let i = self
+            .symbol_ranges
+            .partition_point(|(first, _, _)| *first <= a)
+            .checked_sub(1)?;
+        let (first, last, id) = self.symbol_ranges[i];
+        (a <= last).then(|| id + (a as u32 - first as u32) as usize)
+    }
+
+    fn initial_index(&self) -> Option<usize> {
+        self.initial
+    }
+
+    fn is_final_index(&self, state: usize) -> bool {
+        self.finals[state]
+    }
+
+    fn next_index(&self, state: usize, a: char) -> Option<usize> {
+        let a = self.symbol_index(a)?;
+        self.delta[state * self.alphabets.len() + a]
+    }
+
+    /// Indices of the states that can be reached from the initial state, in
+    /// declaration order.
+    fn reachable_indices(&self) -> Vec<usize> {
+        let mut seen = vec![false; self.states.len()];
+        let mut stack = Vec::new();
+        if let Some(i) = self.initial_index() {
+            seen[i] = true;
+            stack.push(i);
+        }
+        while let Some(i) = stack.pop() {
+            for a in &self.alphabets {
+                if let Some(j) = self.next_index(i, *a) {
+                    if !seen[j] {
+                        seen[j] = true;
+                        stack.push(j);
+                    }
+                }
+            }
+        }
+        (0..self.states.len()).filter(|i| seen[*i]).collect()
+    }
+
+    /// Indices of the states from which a final state can be reached, in
+    /// declaration order.
+    fn coreachable_indices(&self) -> Vec<usize> {
+        let mut predecessors = vec![Vec::new(); self.states.len()];
+        for i in 0..self.states.len() {
+            for a in &self.alphabets {
+                if let Some(j) = self.next_index(i, *a) {
+                    predecessors[j].push(i);
+                }
             }
-            // dbg!("get here");
-            curr_state = cs.unwrap().clone().to_string();
         }
+        let mut seen: Vec<bool> = (0..self.states.len())
+            .map(|i| self.is_final_index(i))
+            .collect();
+        let mut stack: Vec<usize> = (0..self.states.len()).filter(|i| seen[*i]).collect();
+        while let Some(j) = stack.pop() {
+            for i in &predecessors[j] {
+                if !seen[*i] {
+                    seen[*i] = true;
+                    stack.push(*i);
+                }
+            }
+        }
+        (0..self.states.len()).filter(|i| seen[*i]).collect()
+    }
+
+    /// Same automata with the states renamed `q0`, `q1`, ... in declaration
+    /// order, for automata built by an algorithm rather than by hand.
+    fn numbered(&self) -> FiniteAutomata {
+        let names: Vec<String> = (0..self.states.len()).map(|i| format!("q{}", i)).collect();
+        let mut fa = FiniteAutomata::new();
+        fa.rejects_unknown = self.rejects_unknown;
+        fa.add_alphabets(&self.alphabets);
+        fa.add_states(&names);
+        if let Some(i) = self.initial_index() {
+            fa.initial_state(names[i].clone());
+        }
+        for i in 0..self.states.len() {
+            if self.is_final_index(i) {
+                fa.final_state(names[i].clone());
+            }
+            for a in &self.alphabets {
+                if let Some(j) = self.next_index(i, *a) {
+                    fa.add_action(names[i].clone(), &[*a], names[j].clone());
+                }
+            }
+        }
+        fa
+    }
+}
 
-        Some(curr_state == self.final_state)
+fn symbol_ranges(alphabets: &[char]) -> Vec<(char, char, usize)> {
+    let mut symbols: Vec<(char, usize)> = alphabets.iter().copied().zip(0..).collect();
+    symbols.sort();
+    symbols.dedup_by_key(|(a, _)| *a);
+    let mut ranges: Vec<(char, char, usize)> = Vec::new();
+    for (a, id) in symbols {
+        match ranges.last_mut() {
+            Some((first, last, first_id))
+                if *last as u32 + 1 == a as u32
+                    && *first_id + (a as u32 - *first as u32) as usize == id =>
+            {
+                *last = a
+            }
+            _ => ranges.push((a, a, id)),
+        }
     }
+    ranges
 }
 
 #[cfg(test)]
@@ -92,4 +332,77 @@ mod automata_tests {
         assert!(result.is_some());
         assert!(result.unwrap());
     }
+
+    #[test]
+    fn multiple_final_states() {
+        // contains 00 or ends in 1
+        let alphas = ['0', '1'];
+        let states: Vec<String> = ["A", "B", "C", "D"].iter().map(|s| s.to_string()).collect();
+        let mut fa = FiniteAutomata::new();
+        fa.add_alphabets(&alphas);
+        fa.add_states(&states);
+        fa.initial_state(states[0].clone());
+        fa.final_states(&[states[2].clone(), states[3].clone()]);
+        fa.add_actions(vec![
+            (states[0].clone(), &alphas[0..1], states[1].clone()),
+            (states[0].clone(), &alphas[1..2], states[3].clone()),
+            (states[1].clone(), &alphas[0..1], states[2].clone()),
+            (states[1].clone(), &alphas[1..2], states[3].clone()),
+            (states[2].clone(), &alphas[0..2], states[2].clone()),
+            (states[3].clone(), &alphas[0..1], states[1].clone()),
+            (states[3].clone(), &alphas[1..2], states[3].clone()),
+        ]);
+        assert_eq!(Some(true), fa.exec("1001".chars()));
+        assert_eq!(Some(true), fa.exec("0101".chars()));
+        assert_eq!(Some(false), fa.exec("0110".chars()));
+        assert_eq!(Some(false), fa.exec("".chars()));
+    }
+
+    #[test]
+    fn declarations() {
+        let states: Vec<String> = ["A", "B"].iter().map(|s| s.to_string()).collect();
+        let mut fa = FiniteAutomata::new();
+        fa.add_states(&states);
+        fa.add_alphabets(&['0']);
+        fa.initial_state(states[0].clone());
+        fa.final_state(states[1].clone());
+        fa.add_action(states[0].clone(), &['0'], states[1].clone());
+        // unknown names are skipped
+        fa.add_action(states[0].clone(), &['0'], "C".to_string());
+        fa.add_action(states[1].clone(), &['1'], states[0].clone());
+        assert_eq!(Some(true), fa.exec("0".chars()));
+        assert_eq!(None, fa.exec("01".chars()));
+
+        // declaring more symbols and states keeps the transitions
+        fa.add_alphabets(&['1', '0']);
+        fa.add_states(&[states[1].clone(), "C".to_string(), states[0].clone()]);
+        fa.add_action(states[1].clone(), &['1'], states[0].clone());
+        assert_eq!(Some(false), fa.exec("01".chars()));
+        assert_eq!(Some(true), fa.exec("010".chars()));
+    }
+
+    #[test]
+    fn symbol_ranges() {
+        let alphas: Vec<char> = ('a'..='z').chain(['0', '2', '1']).collect();
+        assert_eq!(
+            vec![
+                ('0', '0', 26),
+                ('1', '1', 28),
+                ('2', '2', 27),
+                ('a', 'z', 0)
+            ],
+            super::symbol_ranges(&alphas)
+        );
+        let states = vec!["A".to_owned(), "B".to_owned()];
+        let mut fa = FiniteAutomata::new();
+        fa.add_alphabets(&alphas);
+        fa.add_states(&states);
+        fa.initial_state(states[0].clone());
+        fa.final_state(states[1].clone());
+        fa.add_action(states[0].clone(), &alphas[..26], states[1].clone());
+        fa.add_action(states[1].clone(), &alphas[26..], states[1].clone());
+        assert_eq!(Some(true), fa.exec("x102".chars()));
+        assert_eq!(None, fa.exec("xy".chars()));
+        assert_eq!(None, fa.exec("3".chars()));
+    }
 }
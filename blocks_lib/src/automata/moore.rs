@@ -0,0 +1,146 @@
+use std::{collections::HashMap, str::Chars};
+
+use super::MealyMachine;
+
+/// Transducer whose output depends only on the state. Every state has a
+/// label, running it writes the label of each state it goes through,
+/// starting with the initial one.
+#[derive(Default, Debug, Clone)]
+pub struct MooreMachine {
+    alphabets: Vec<char>,
+    states: Vec<String>,
+    initial_state: String,
+    outputs: HashMap<String, String>,
+    delta: HashMap<(String, char), String>,
+}
+
+impl MooreMachine {
+    pub fn new() -> Self {
+        Self {
+            ..Default::default()
+        }
+    }
+
+    pub fn add_alphabets(&mut self, alpha: &[char]) {
+        self.alphabets = Vec::from(alpha);
+    }
+
+    pub fn add_states(&mut self, states: &[String]) {
+        self.states = Vec::from(states);
+    }
+
+    pub fn initial_state(&mut self, state: String) {
+        self.initial_state = state;
+    }
+
+    /// Label written whenever `state` is entered.
+    pub fn state_output(&mut self, state: String, output: String) {
+        self.outputs.insert(state, output);
+    }
+
+    pub fn add_action(&mut self, state1: String, alpha: &[char], state2: String) {
+        if !self.states.contains(&state1) || !self.states.contains(&state2) {
+            return;
+        }
+        for a in alpha {
+            self.delta.insert((state1.clone(), *a), state2.clone());
+        }
+    }
+
+    pub fn add_actions(&mut self, actions: Vec<(String, &[char], String)>) {
+        for action in actions {
+            let (state1, alpha, state2) = action;
+            self.add_action(state1, alpha, state2);
+        }
+    }
+
+    /// Labels of the states visited while reading `input`, one more than the
+    /// number of symbols. `None` when some symbol has no transition.
+    pub fn exec(&self, input: Chars) -> Option<String> {
+        let mut curr_state = &self.initial_state;
+        let mut output = self.output(curr_state).to_owned();
+        for a in input {
+            curr_state = self.delta.get(&(curr_state.clone(), a))?;
+            output.push_str(self.output(curr_state));
+        }
+        Some(output)
+    }
+
+    pub fn states(&self) -> &[String] {
+        &self.states
+    }
+
+    pub fn alphabets(&self) -> &[char] {
+        &self.alphabets
+    }
+
+    /// Equivalent Mealy machine with the same states, each transition writes
+    /// the label of the state it enters. The label of the initial state has
+    /// no transition to go on, so it is the only output lost.
+    pub fn to_mealy(&self) -> MealyMachine {
+        let mut mealy = MealyMachine::new();
+        mealy.add_alphabets(&self.alphabets);
+        mealy.add_states(&self.states);
+        mealy.initial_state(self.initial_state.clone());
+        for ((state1, a), state2) in &self.delta {
+            let output = self.output(state2).to_owned();
+            mealy.add_action(state1.clone(), &[*a], output, state2.clone());
+        }
+        mealy
+    }
+
+    fn output(&self, state: &str) -> &str {
+        self.outputs.get(state).map_or("", String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod moore_tests {
+    use super::MooreMachine;
+
+    // traffic light moving on every tick `t` and going back to red on `r`
+    fn traffic_light() -> MooreMachine {
+        let alphas = ['t', 'r'];
+        let states = vec!["R".to_owned(), "G".to_owned(), "Y".to_owned()];
+        let mut moore = MooreMachine::new();
+        moore.add_alphabets(&alphas);
+        moore.add_states(&states);
+        moore.initial_state(states[0].clone());
+        for (state, output) in states.iter().zip(["r", "g", "y"]) {
+            moore.state_output(state.clone(), output.to_owned());
+        }
+        moore.add_actions(vec![
+            (states[0].clone(), &alphas[0..1], states[1].clone()),
+            (states[1].clone(), &alphas[0..1], states[2].clone()),
+            (states[2].clone(), &alphas[0..1], states[0].clone()),
+            (states[0].clone(), &alphas[1..2], states[0].clone()),
+            (states[1].clone(), &alphas[1..2], states[0].clone()),
+            (states[2].clone(), &alphas[1..2], states[0].clone()),
+        ]);
+        moore
+    }
+
+    #[test]
+    fn basic() {
+        let moore = traffic_light();
+        assert_eq!(Some("r".to_owned()), moore.exec("".chars()));
+        assert_eq!(Some("rgyrg".to_owned()), moore.exec("tttt".chars()));
+        assert_eq!(Some("rgrg".to_owned()), moore.exec("trt".chars()));
+        assert_eq!(None, moore.exec("tx".chars()));
+    }
+
+    #[test]
+    fn to_mealy() {
+        let moore = traffic_light();
+        let mealy = moore.to_mealy();
+        for input in ["", "t", "tttt", "trt", "ttrtt"] {
+            let output = moore.exec(input.chars()).unwrap();
+            assert_eq!(Some(output[1..].to_owned()), mealy.exec(input.chars()));
+        }
+
+        let back = mealy.to_moore();
+        for input in ["", "t", "tttt", "trt", "ttrtt"] {
+            assert_eq!(mealy.exec(input.chars()), back.exec(input.chars()));
+        }
+    }
+}
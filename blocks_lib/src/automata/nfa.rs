@@ -0,0 +1,315 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    str::Chars,
+};
+
+use super::FiniteAutomata;
+
+#[derive(Default, Debug)]
+pub struct NondeterministicAutomata {
+    alphabets: Vec<char>,
+    states: Vec<String>,
+    initial_states: HashSet<String>,
+    final_states: HashSet<String>,
+    delta: HashMap<(String, char), HashSet<String>>,
+    epsilon: HashMap<String, HashSet<String>>,
+}
+
+impl NondeterministicAutomata {
+    pub fn new() -> Self {
+        Self {
+            ..Default::default()
+        }
+    }
+
+    pub fn add_alphabets(&mut self, alpha: &[char]) {
+        self.alphabets = Vec::from(alpha);
+    }
+
+    pub fn add_states(&mut self, states: &[String]) {
+        self.states = Vec::from(states);
+    }
+
+    pub fn initial_state(&mut self, state: String) {
+        self.initial_states.insert(state);
+    }
+
+    pub fn initial_states(&mut self, states: &[String]) {
+        self.initial_states = states.iter().cloned().collect();
+    }
+
+    pub fn final_state(&mut self, state: String) {
+        self.final_states.insert(state);
+    }
+
+    pub fn final_states(&mut self, states: &[String]) {
+        self.final_states = states.iter().cloned().collect();
+    }
+
+    pub fn add_action(&mut self, state1: String, alpha: &[char], states2: &[String]) {
+        if !self.states.contains(&state1) || states2.iter().any(|s| !self.states.contains(s)) {
+            return;
+        }
+        for a in alpha {
+            let targets = self.delta.entry((state1.clone(), *a)).or_default();
+            targets.extend(states2.iter().cloned());
+        }
+    }
+
+    pub fn add_epsilon_action(&mut self, state1: String, states2: &[String]) {
+        if !self.states.contains(&state1) || states2.iter().any(|s| !self.states.contains(s)) {
+            return;
+        }
+        let targets = self.epsilon.entry(state1).or_default();
+        targets.extend(states2.iter().cloned());
+    }
+
+    pub fn add_actions(&mut self, actions: Vec<(String, &[char], Vec<String>)>) {
+        for action in actions {
+            let (state1, alpha, states2) = action;
+            self.add_action(state1, alpha, &states2);
+        }
+    }
+
+    pub fn exec(&self, input: Chars) -> bool {
+        let mut curr_states = self.epsilon_closure(&self.initial_states);
+        for a in input {
+            curr_states = self.epsilon_closure(&self.step(&curr_states, a));
+            if curr_states.is_empty() {
+                return false;
+            }
+        }
+        curr_states.iter().any(|s| self.final_states.contains(s))
+    }
+
+    pub fn states(&self) -> &[String] {
+        &self.states
+    }
+
+    pub fn alphabets(&self) -> &[char] {
+        &self.alphabets
+    }
+
+    /// All the states reachable from `states` using only ε-moves, including
+    /// `states` themselves.
+    pub fn epsilon_closure(&self, states: &HashSet<String>) -> HashSet<String> {
+        let mut closure = states.clone();
+        let mut stack: Vec<&String> = states.iter().collect();
+        while let Some(s) = stack.pop() {
+            if let Some(targets) = self.epsilon.get(s) {
+                for t in targets {
+                    if closure.insert(t.clone()) {
+                        stack.push(t);
+                    }
+                }
+            }
+        }
+        closure
+    }
+
+    /// States that can be reached from an initial state, in declaration order.
+    pub fn reachable_states(&self) -> Vec<String> {
+        let mut seen = self.epsilon_closure(&self.initial_states);
+        let mut queue: VecDeque<String> = seen.iter().cloned().collect();
+        while let Some(s) = queue.pop_front() {
+            for a in &self.alphabets {
+                let next = self.epsilon_closure(&self.step(&HashSet::from([s.clone()]), *a));
+                for t in next {
+                    if seen.insert(t.clone()) {
+                        queue.push_back(t);
+                    }
+                }
+            }
+        }
+        self.ordered(&seen)
+    }
+
+    /// States from which a final state can be reached, in declaration order.
+    pub fn coreachable_states(&self) -> Vec<String> {
+        let mut predecessors: HashMap<&String, Vec<&String>> = HashMap::new();
+        let moves = self.delta.iter().map(|((s, _), targets)| (s, targets));
+        for (s, targets) in moves.chain(&self.epsilon) {
+            for t in targets {
+                predecessors.entry(t).or_default().push(s);
+            }
+        }
+        let mut seen = self.final_states.clone();
+        let mut stack: Vec<String> = seen.iter().cloned().collect();
+        while let Some(s) = stack.pop() {
+            for p in predecessors.get(&s).into_iter().flatten() {
+                if seen.insert((*p).clone()) {
+                    stack.push((*p).clone());
+                }
+            }
+        }
+        self.ordered(&seen)
+    }
+
+    /// Builds an equivalent automata without ε-moves. A state gets the moves of
+    /// every state in its ε-closure and becomes final if its closure holds a
+    /// final state.
+    pub fn remove_epsilon(&self) -> NondeterministicAutomata {
+        let mut nfa = NondeterministicAutomata::new();
+        nfa.add_alphabets(&self.alphabets);
+        nfa.add_states(&self.states);
+        nfa.initial_states = self.initial_states.clone();
+        for s in &self.states {
+            let closure = self.epsilon_closure(&HashSet::from([s.clone()]));
+            if closure.iter().any(|c| self.final_states.contains(c)) {
+                nfa.final_state(s.clone());
+            }
+            for a in &self.alphabets {
+                let targets = self.ordered(&self.step(&closure, *a));
+                if !targets.is_empty() {
+                    nfa.add_action(s.clone(), &[*a], &targets);
+                }
+            }
+        }
+        nfa
+    }
+
+    /// Subset construction. Every state of the resulting automata is a set of
+    /// states of `self`, named like `{A,B}` in declaration order. The empty set
+    /// `{}` only shows up when some subset has no move on a symbol.
+    pub fn determinize(&self) -> FiniteAutomata {
+        let mut fa = FiniteAutomata::new();
+        fa.add_alphabets(&self.alphabets);
+
+        let initial = self.ordered(&self.epsilon_closure(&self.initial_states));
+        let mut names = vec![self.subset_name(&initial)];
+        let mut subsets = vec![initial];
+        let mut queue = VecDeque::from([0]);
+        let mut actions = Vec::new();
+        while let Some(i) = queue.pop_front() {
+            let curr: HashSet<String> = subsets[i].iter().cloned().collect();
+            for a in &self.alphabets {
+                let next = self.ordered(&self.epsilon_closure(&self.step(&curr, *a)));
+                let j = match subsets.iter().position(|s| *s == next) {
+                    Some(j) => j,
+                    None => {
+                        names.push(self.subset_name(&next));
+                        subsets.push(next);
+                        queue.push_back(subsets.len() - 1);
+                        subsets.len() - 1
+                    }
+                };
+                actions.push((i, *a, j));
+            }
+        }
+
+        fa.add_states(&names);
+        fa.initial_state(names[0].clone());
+        for (subset, name) in subsets.iter().zip(&names) {
+            if subset.iter().any(|s| self.final_states.contains(s)) {
+                fa.final_state(name.clone());
+            }
+        }
+        for (i, a, j) in actions {
+            fa.add_action(names[i].clone(), &[a], names[j].clone());
+        }
+        fa
+    }
+
+    pub(super) fn step(&self, states: &HashSet<String>, a: char) -> HashSet<String> {
+        let mut next = HashSet::new();
+        for s in states {
+            if let Some(targets) = self.delta.get(&(s.clone(), a)) {
+                next.extend(targets.iter().cloned());
+            }
+        }
+        next
+    }
+
+    fn ordered(&self, states: &HashSet<String>) -> Vec<String> {
+        self.states
+            .iter()
+            .filter(|s| states.contains(*s))
+            .cloned()
+            .collect()
+    }
+
+    fn subset_name(&self, states: &[String]) -> String {
+        format!("{{{}}}", states.join(","))
+    }
+}
+
+#[cfg(test)]
+mod nfa_tests {
+    use super::super::fixtures::nfa_from_table;
+    use super::NondeterministicAutomata;
+
+    fn second_last_one() -> NondeterministicAutomata {
+        nfa_from_table(
+            &['0', '1'],
+            &["C"],
+            &[
+                ("A", '0', "A"),
+                ("A", '1', "A"),
+                ("A", '1', "B"),
+                ("B", '0', "C"),
+                ("B", '1', "C"),
+            ],
+        )
+    }
+
+    #[test]
+    fn basic() {
+        let nfa = second_last_one();
+        assert!(nfa.exec("0110".chars()));
+        assert!(nfa.exec("11".chars()));
+        assert!(!nfa.exec("1001".chars()));
+        assert!(!nfa.exec("1".chars()));
+    }
+
+    #[test]
+    fn determinize() {
+        let nfa = second_last_one();
+        let fa = nfa.determinize();
+        assert_eq!(vec!["{A}", "{A,B}", "{A,C}", "{A,B,C}"], fa.states);
+        for input in ["", "0", "1", "10", "11", "0110", "1001", "111", "0101"] {
+            assert_eq!(Some(nfa.exec(input.chars())), fa.exec(input.chars()));
+        }
+        assert_eq!(Some(true), fa.exec("10".chars()));
+        assert_eq!(Some(false), fa.exec("01".chars()));
+    }
+
+    // ends in 01 or is made only of zeros
+    fn with_epsilon() -> NondeterministicAutomata {
+        nfa_from_table(
+            &['0', '1'],
+            &["C", "Z"],
+            &[
+                ("S", 'ε', "A"),
+                ("A", '0', "A"),
+                ("A", '1', "A"),
+                ("A", '0', "B"),
+                ("B", '1', "C"),
+                ("S", 'ε', "Z"),
+                ("Z", '0', "Z"),
+            ],
+        )
+    }
+
+    #[test]
+    fn epsilon() {
+        let nfa = with_epsilon();
+        assert!(nfa.exec("".chars()));
+        assert!(nfa.exec("000".chars()));
+        assert!(nfa.exec("1101".chars()));
+        assert!(!nfa.exec("0010".chars()));
+
+        let closure = nfa.epsilon_closure(&["S".to_string()].into());
+        assert_eq!(
+            closure,
+            ["S", "A", "Z"].iter().map(|s| s.to_string()).collect()
+        );
+
+        let free = nfa.remove_epsilon();
+        assert!(free.epsilon.is_empty());
+        let fa = nfa.determinize();
+        for input in ["", "0", "1", "01", "000", "1101", "0010", "0101", "10"] {
+            assert_eq!(nfa.exec(input.chars()), free.exec(input.chars()));
+            assert_eq!(Some(nfa.exec(input.chars())), fa.exec(input.chars()));
+        }
+    }
+}
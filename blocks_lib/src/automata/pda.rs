@@ -0,0 +1,266 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    str::Chars,
+};
+
+use super::DEFAULT_STEP_LIMIT;
+
+/// When a pushdown automata accepts an input it has read entirely.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Acceptance {
+    #[default]
+    FinalState,
+    EmptyStack,
+}
+
+/// Nondeterministic pushdown automata. A transition reads an input symbol or
+/// nothing, pops the top of the stack or nothing, and pushes a word whose
+/// first symbol ends up on top.
+#[derive(Debug, Clone)]
+pub struct PushdownAutomata {
+    alphabets: Vec<char>,
+    stack_alphabets: Vec<char>,
+    states: Vec<String>,
+    initial_state: String,
+    initial_stack: Vec<char>,
+    final_states: HashSet<String>,
+    acceptance: Acceptance,
+    step_limit: usize,
+    // `None` reads no input or pops nothing
+    #[allow(clippy::type_complexity)]
+    delta: HashMap<(String, Option<char>, Option<char>), Vec<(String, Vec<char>)>>,
+}
+
+impl Default for PushdownAutomata {
+    fn default() -> Self {
+        Self {
+            alphabets: Vec::new(),
+            stack_alphabets: Vec::new(),
+            states: Vec::new(),
+            initial_state: String::new(),
+            initial_stack: Vec::new(),
+            final_states: HashSet::new(),
+            acceptance: Acceptance::default(),
+            step_limit: DEFAULT_STEP_LIMIT,
+            delta: HashMap::new(),
+        }
+    }
+}
+
+impl PushdownAutomata {
+    pub fn new() -> Self {
+        Self {
+            ..Default::default()
+        }
+    }
+
+    pub fn add_alphabets(&mut self, alpha: &[char]) {
+        self.alphabets = Vec::from(alpha);
+    }
+
+    pub fn add_stack_alphabets(&mut self, alpha: &[char]) {
+        self.stack_alphabets = Vec::from(alpha);
+    }
+
+    pub fn add_states(&mut self, states: &[String]) {
+        self.states = Vec::from(states);
+    }
+
+    pub fn initial_state(&mut self, state: String) {
+        self.initial_state = state;
+    }
+
+    /// Stack at the start of a run, top first.
+    pub fn initial_stack(&mut self, stack: &[char]) {
+        self.initial_stack = Vec::from(stack);
+    }
+
+    pub fn final_state(&mut self, state: String) {
+        self.final_states.insert(state);
+    }
+
+    pub fn final_states(&mut self, states: &[String]) {
+        self.final_states = states.iter().cloned().collect();
+    }
+
+    pub fn accept_by(&mut self, acceptance: Acceptance) {
+        self.acceptance = acceptance;
+    }
+
+    /// Bound on the configurations [`PushdownAutomata::exec`] explores.
+    pub fn step_limit(&mut self, limit: usize) {
+        self.step_limit = limit;
+    }
+
+    /// Adds a move from `state1` to `state2` reading `alpha` and popping
+    /// `top`, `None` for either reads or pops nothing. `push` is written on
+    /// the stack, its first symbol on top. Undeclared states or symbols are
+    /// ignored.
+    pub fn add_action(
+        &mut self,
+        state1: String,
+        alpha: Option<char>,
+        top: Option<char>,
+        push: &[char],
+        state2: String,
+    ) {
+        if !self.states.contains(&state1)
+            || !self.states.contains(&state2)
+            || alpha.is_some_and(|a| !self.alphabets.contains(&a))
+            || top
+                .iter()
+                .chain(push)
+                .any(|s| !self.stack_alphabets.contains(s))
+        {
+            return;
+        }
+        let moves = self.delta.entry((state1, alpha, top)).or_default();
+        let next = (state2, Vec::from(push));
+        if !moves.contains(&next) {
+            moves.push(next);
+        }
+    }
+
+    /// Breadth first search over the configurations reachable on `input`.
+    /// `Some(true)` as soon as one accepts, `Some(false)` when none can, and
+    /// `None` when the step limit is reached first, which happens for
+    /// instance when ε-moves keep growing the stack.
+    pub fn exec(&self, input: Chars) -> Option<bool> {
+        let input: Vec<char> = input.collect();
+        // the stack is stored top last
+        let mut stack = self.initial_stack.clone();
+        stack.reverse();
+        let initial = (self.initial_state.clone(), 0, stack);
+        let mut seen = HashSet::from([initial.clone()]);
+        let mut queue = VecDeque::from([initial]);
+        let mut steps = 0;
+        while let Some((state, pos, stack)) = queue.pop_front() {
+            if pos == input.len() && self.accepts(&state, &stack) {
+                return Some(true);
+            }
+            if steps == self.step_limit {
+                return None;
+            }
+            steps += 1;
+
+            let mut reads = vec![(None, pos)];
+            if let Some(a) = input.get(pos) {
+                reads.push((Some(*a), pos + 1));
+            }
+            let mut tops = vec![None];
+            if let Some(top) = stack.last() {
+                tops.push(Some(*top));
+            }
+            for (alpha, next_pos) in reads {
+                for pops in tops.iter().copied() {
+                    let moves = match self.delta.get(&(state.clone(), alpha, pops)) {
+                        Some(moves) => moves,
+                        None => continue,
+                    };
+                    for (next, push) in moves {
+                        let mut next_stack = stack.clone();
+                        if pops.is_some() {
+                            next_stack.pop();
+                        }
+                        next_stack.extend(push.iter().rev());
+                        let config = (next.clone(), next_pos, next_stack);
+                        if seen.insert(config.clone()) {
+                            queue.push_back(config);
+                        }
+                    }
+                }
+            }
+        }
+        Some(false)
+    }
+
+    pub fn states(&self) -> &[String] {
+        &self.states
+    }
+
+    pub fn alphabets(&self) -> &[char] {
+        &self.alphabets
+    }
+
+    pub fn stack_alphabets(&self) -> &[char] {
+        &self.stack_alphabets
+    }
+
+    fn accepts(&self, state: &String, stack: &[char]) -> bool {
+        match self.acceptance {
+            Acceptance::FinalState => self.final_states.contains(state),
+            Acceptance::EmptyStack => stack.is_empty(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod pda_tests {
+    use super::{Acceptance, PushdownAutomata};
+
+    // state, input, popped symbol, pushed word and next state
+    type Move<'a> = (&'a str, Option<char>, Option<char>, &'a str, &'a str);
+
+    fn pda(states: &[&str], moves: &[Move]) -> PushdownAutomata {
+        let states: Vec<String> = states.iter().map(|s| s.to_string()).collect();
+        let mut pda = PushdownAutomata::new();
+        pda.add_alphabets(&['a', 'b']);
+        pda.add_stack_alphabets(&['Z', 'X']);
+        pda.add_states(&states);
+        pda.initial_state(states[0].clone());
+        pda.initial_stack(&['Z']);
+        for (state1, alpha, top, push, state2) in moves {
+            let push: Vec<char> = push.chars().collect();
+            pda.add_action(state1.to_string(), *alpha, *top, &push, state2.to_string());
+        }
+        pda
+    }
+
+    // a^n b^n, guessing when the b start
+    fn a_n_b_n() -> PushdownAutomata {
+        let mut pda = pda(
+            &["A", "B", "C"],
+            &[
+                ("A", Some('a'), None, "X", "A"),
+                ("A", None, None, "", "B"),
+                ("B", Some('b'), Some('X'), "", "B"),
+                ("B", None, Some('Z'), "Z", "C"),
+            ],
+        );
+        pda.final_state("C".to_string());
+        pda
+    }
+
+    #[test]
+    fn final_state() {
+        let pda = a_n_b_n();
+        for (input, accepted) in [
+            ("", true),
+            ("ab", true),
+            ("aaabbb", true),
+            ("aab", false),
+            ("ba", false),
+        ] {
+            assert_eq!(Some(accepted), pda.exec(input.chars()), "{}", input);
+        }
+    }
+
+    #[test]
+    fn empty_stack() {
+        let mut pda = a_n_b_n();
+        pda.add_action("C".to_string(), None, Some('Z'), &[], "C".to_string());
+        pda.final_states(&[]);
+        pda.accept_by(Acceptance::EmptyStack);
+        assert_eq!(Some(true), pda.exec("aabb".chars()));
+        assert_eq!(Some(false), pda.exec("abb".chars()));
+    }
+
+    #[test]
+    fn step_limit() {
+        // pushes forever without reading
+        let mut pda = pda(&["A", "B"], &[("A", None, None, "X", "A")]);
+        pda.final_state("B".to_string());
+        pda.step_limit(100);
+        assert_eq!(None, pda.exec("".chars()));
+    }
+}
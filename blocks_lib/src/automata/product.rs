@@ -0,0 +1,180 @@
+use std::collections::VecDeque;
+
+use super::FiniteAutomata;
+
+impl FiniteAutomata {
+    pub fn intersection(&self, other: &FiniteAutomata) -> FiniteAutomata {
+        self.product(other, |a, b| a && b)
+    }
+
+    pub fn union(&self, other: &FiniteAutomata) -> FiniteAutomata {
+        self.product(other, |a, b| a || b)
+    }
+
+    pub fn difference(&self, other: &FiniteAutomata) -> FiniteAutomata {
+        self.product(other, |a, b| a && !b)
+    }
+
+    pub fn symmetric_difference(&self, other: &FiniteAutomata) -> FiniteAutomata {
+        self.product(other, |a, b| a != b)
+    }
+
+    /// Runs both automata side by side over the union of their alphabets. A
+    /// side without a transition falls into a trap shown as `∅`, so the states
+    /// are named like `(A,B)` or `(A,∅)`. Only the reachable pairs are kept,
+    /// minus the trapped ones that `accept` can never turn final. Symbols
+    /// outside both alphabets are rejected when the sides rejecting them
+    /// settle the outcome whatever the others would say.
+    fn product(
+        &self,
+        other: &FiniteAutomata,
+        accept: impl Fn(bool, bool) -> bool,
+    ) -> FiniteAutomata {
+        let mut alphabets = self.alphabets.clone();
+        for a in &other.alphabets {
+            if !alphabets.contains(a) {
+                alphabets.push(*a);
+            }
+        }
+        // once a side is trapped only the other one decides acceptance
+        let alive = |pair: &(Option<usize>, Option<usize>)| match pair {
+            (None, None) => false,
+            (None, Some(_)) => accept(false, true),
+            (Some(_), None) => accept(true, false),
+            _ => true,
+        };
+        let name = |pair: &(Option<usize>, Option<usize>)| {
+            let left = pair.0.map_or("∅", |i| self.states[i].as_str());
+            let right = pair.1.map_or("∅", |i| other.states[i].as_str());
+            format!("({},{})", left, right)
+        };
+
+        let initial = (self.initial_index(), other.initial_index());
+        let mut pairs = vec![initial];
+        let mut queue = VecDeque::from([0]);
+        let mut actions = Vec::new();
+        while let Some(i) = queue.pop_front() {
+            let (left, right) = pairs[i];
+            for a in &alphabets {
+                let next = (
+                    left.and_then(|l| self.next_index(l, *a)),
+                    right.and_then(|r| other.next_index(r, *a)),
+                );
+                if !alive(&next) {
+                    continue;
+                }
+                let j = match pairs.iter().position(|p| *p == next) {
+                    Some(j) => j,
+                    None => {
+                        pairs.push(next);
+                        queue.push_back(pairs.len() - 1);
+                        pairs.len() - 1
+                    }
+                };
+                actions.push((i, *a, j));
+            }
+        }
+
+        let names: Vec<String> = pairs.iter().map(name).collect();
+        let mut fa = FiniteAutomata::new();
+        let outcomes = |rejects: bool| {
+            if rejects {
+                vec![false]
+            } else {
+                vec![false, true]
+            }
+        };
+        fa.rejects_unknown = outcomes(self.rejects_unknown).into_iter().all(|left| {
+            outcomes(other.rejects_unknown)
+                .into_iter()
+                .all(|right| !accept(left, right))
+        });
+        fa.add_alphabets(&alphabets);
+        fa.add_states(&names);
+        fa.initial_state(names[0].clone());
+        for (pair, name) in pairs.iter().zip(&names) {
+            let left = pair.0.is_some_and(|l| self.is_final_index(l));
+            let right = pair.1.is_some_and(|r| other.is_final_index(r));
+            if accept(left, right) {
+                fa.final_state(name.clone());
+            }
+        }
+        for (i, a, j) in actions {
+            fa.add_action(names[i].clone(), &[a], names[j].clone());
+        }
+        fa
+    }
+}
+
+#[cfg(test)]
+mod product_tests {
+    use crate::automata::fixtures::{from_table as fa, last_one};
+
+    #[test]
+    fn basic() {
+        let last_one = last_one();
+        // starts with 1
+        let first_one = fa(
+            &['0', '1'],
+            &["B"],
+            &[("A", '1', "B"), ("B", '0', "B"), ("B", '1', "B")],
+        );
+
+        let both = last_one.intersection(&first_one);
+        let either = last_one.union(&first_one);
+        let only_last = last_one.difference(&first_one);
+        let one_of = last_one.symmetric_difference(&first_one);
+        for input in ["", "0", "1", "01", "10", "11", "001", "100", "101"] {
+            let l = last_one.exec(input.chars()) == Some(true);
+            let f = first_one.exec(input.chars()) == Some(true);
+            assert_eq!(Some(l && f), both.exec(input.chars()).or(Some(false)));
+            assert_eq!(Some(l || f), either.exec(input.chars()));
+            assert_eq!(Some(l && !f), only_last.exec(input.chars()).or(Some(false)));
+            assert_eq!(Some(l != f), one_of.exec(input.chars()));
+        }
+        assert!(both.states.iter().all(|s| !s.contains('∅')));
+    }
+
+    #[test]
+    fn rejects_unknown() {
+        let mut last_one = last_one();
+        last_one.reject_unknown(true);
+        let first_one = fa(&['0', '1'], &["B"], &[("A", '1', "B"), ("B", '1', "B")]);
+        // 2 is rejected whatever the other side says, unless it may accept
+        assert_eq!(
+            Some(false),
+            last_one.intersection(&first_one).exec("2".chars())
+        );
+        assert_eq!(
+            Some(false),
+            first_one.intersection(&last_one).exec("2".chars())
+        );
+        assert_eq!(
+            Some(false),
+            last_one.difference(&first_one).exec("2".chars())
+        );
+        assert_eq!(None, first_one.difference(&last_one).exec("2".chars()));
+        assert_eq!(None, last_one.union(&first_one).exec("2".chars()));
+        assert_eq!(Some(false), last_one.union(&last_one).exec("2".chars()));
+        assert_eq!(None, last_one.complement().exec("2".chars()));
+        assert_eq!(Some(false), last_one.minimize().exec("12".chars()));
+    }
+
+    #[test]
+    fn different_alphabets() {
+        // a then anything made of a and b
+        let ab = fa(
+            &['a', 'b'],
+            &["B"],
+            &[("A", 'a', "B"), ("B", 'a', "B"), ("B", 'b', "B")],
+        );
+        // only c
+        let c = fa(&['c'], &["A"], &[("A", 'c', "A")]);
+        let either = ab.union(&c);
+        assert_eq!(vec!['a', 'b', 'c'], either.alphabets);
+        assert_eq!(Some(true), either.exec("ab".chars()));
+        assert_eq!(Some(true), either.exec("ccc".chars()));
+        assert_ne!(Some(true), either.exec("abc".chars()));
+        assert_eq!(Some(true), ab.difference(&c).exec("aab".chars()));
+    }
+}
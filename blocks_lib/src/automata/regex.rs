@@ -0,0 +1,532 @@
+use std::{fmt, iter::Peekable, str::Chars};
+
+use super::symbol_class;
+use super::{FiniteAutomata, NondeterministicAutomata};
+
+/// Syntax tree of a regular expression.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(super) enum Regex {
+    /// Matches only the empty input, written `ε` or `()`.
+    Epsilon,
+    /// Matches any one of the symbols, a literal or a class like `[a-c]`.
+    Symbols(Vec<char>),
+    Concat(Box<Regex>, Box<Regex>),
+    Union(Box<Regex>, Box<Regex>),
+    Star(Box<Regex>),
+    Plus(Box<Regex>),
+    Optional(Box<Regex>),
+}
+
+impl FiniteAutomata {
+    /// Compiles a regular expression into a minimal and complete automata
+    /// over the symbols it mentions, whose states are named `q0`, `q1`, ...
+    /// Inputs with other symbols are rejected.
+    /// The syntax has union `|`, concatenation, `*`,
+    /// `+`, `?`, grouping, classes like `[0-9a]`, `\d` and `\w`, and `\` to
+    /// escape.
+    pub fn from_regex(pattern: &str) -> Option<FiniteAutomata> {
+        let nfa = NondeterministicAutomata::from_regex(pattern)?;
+        let mut fa = nfa.determinize();
+        fa.reject_unknown(true);
+        Some(fa.minimize().numbered())
+    }
+
+    /// Same as [`FiniteAutomata::from_regex`] with symbols spelled as words,
+    /// set apart by whitespace or operators, like `login (read|write)* logout`.
+    /// Each word stands for the char `symbol` gives it, `ε` being the empty
+    /// input, and `\` escapes a character inside a word. Classes are written
+    /// as unions.
+    pub fn from_regex_with(
+        pattern: &str,
+        symbol: impl FnMut(&str) -> char,
+    ) -> Option<FiniteAutomata> {
+        FiniteAutomata::from_regex(&spelled(pattern, symbol)?)
+    }
+}
+
+/// Pattern of words rewritten with a char per word, in the syntax read by
+/// [`FiniteAutomata::from_regex`].
+fn spelled(pattern: &str, mut symbol: impl FnMut(&str) -> char) -> Option<String> {
+    let mut spelled = String::new();
+    // the word being read and whether it had escapes, `\ε` being a symbol
+    let mut word = (String::new(), false);
+    let mut end_word = |word: &mut (String, bool), spelled: &mut String| {
+        match (word.0.as_str(), word.1) {
+            ("", _) => {}
+            ("ε", false) => spelled.push('ε'),
+            (token, _) => {
+                let a = symbol(token);
+                if "()[]|*+?\\/ε".contains(a) {
+                    spelled.push('\\');
+                }
+                spelled.push(a);
+            }
+        }
+        *word = (String::new(), false);
+    };
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                word.0.push(chars.next()?);
+                word.1 = true;
+            }
+            '(' | ')' | '|' | '*' | '+' | '?' => {
+                end_word(&mut word, &mut spelled);
+                spelled.push(c);
+            }
+            c if c.is_whitespace() => end_word(&mut word, &mut spelled),
+            c => word.0.push(c),
+        }
+    }
+    end_word(&mut word, &mut spelled);
+    Some(spelled)
+}
+
+impl NondeterministicAutomata {
+    /// Thompson's construction of a regular expression, see
+    /// [`FiniteAutomata::from_regex`] for the syntax.
+    pub fn from_regex(pattern: &str) -> Option<NondeterministicAutomata> {
+        let mut chars = pattern.chars().peekable();
+        let regex = parse_union(&mut chars)?;
+        if chars.next().is_some() {
+            return None;
+        }
+
+        let mut thompson = Thompson::default();
+        let (start, end) = thompson.build(&regex);
+        let states: Vec<String> = (0..thompson.len).map(|i| format!("q{}", i)).collect();
+        let mut alphabets: Vec<char> = thompson.moves.iter().filter_map(|m| m.1).collect();
+        alphabets.sort();
+        alphabets.dedup();
+
+        let mut nfa = NondeterministicAutomata::new();
+        nfa.add_alphabets(&alphabets);
+        nfa.add_states(&states);
+        nfa.initial_state(states[start].clone());
+        nfa.final_state(states[end].clone());
+        for (state1, a, state2) in thompson.moves {
+            match a {
+                Some(a) => nfa.add_action(states[state1].clone(), &[a], &[states[state2].clone()]),
+                None => nfa.add_epsilon_action(states[state1].clone(), &[states[state2].clone()]),
+            }
+        }
+        Some(nfa)
+    }
+}
+
+/// Constructors that simplify as they go, so that expressions built by state
+/// elimination stay readable.
+impl Regex {
+    pub(super) fn union(self, other: Regex) -> Regex {
+        let mut nullable = false;
+        let mut symbols: Vec<char> = Vec::new();
+        let mut alternatives: Vec<Regex> = Vec::new();
+        for regex in self.alternatives().into_iter().chain(other.alternatives()) {
+            let regex = match regex {
+                Regex::Epsilon => {
+                    nullable = true;
+                    continue;
+                }
+                Regex::Optional(inner) => {
+                    nullable = true;
+                    *inner
+                }
+                regex => regex,
+            };
+            match regex {
+                Regex::Symbols(s) => symbols.extend(s),
+                regex if !alternatives.contains(&regex) => alternatives.push(regex),
+                _ => {}
+            }
+        }
+        if !symbols.is_empty() {
+            symbols.sort();
+            symbols.dedup();
+            alternatives.insert(0, Regex::Symbols(symbols));
+        }
+        let regex = alternatives
+            .into_iter()
+            .reduce(|left, right| Regex::Union(Box::new(left), Box::new(right)));
+        match regex {
+            None => Regex::Epsilon,
+            Some(regex) if nullable => regex.optional(),
+            Some(regex) => regex,
+        }
+    }
+
+    pub(super) fn concat(self, other: Regex) -> Regex {
+        match (self, other) {
+            (Regex::Epsilon, regex) | (regex, Regex::Epsilon) => regex,
+            (left, Regex::Star(right)) if left == *right => Regex::Plus(right),
+            (Regex::Star(left), right) if *left == right => Regex::Plus(left),
+            (left, right) => Regex::Concat(Box::new(left), Box::new(right)),
+        }
+    }
+
+    pub(super) fn star(self) -> Regex {
+        match self {
+            Regex::Epsilon => Regex::Epsilon,
+            Regex::Star(inner) | Regex::Plus(inner) | Regex::Optional(inner) => Regex::Star(inner),
+            regex => Regex::Star(Box::new(regex)),
+        }
+    }
+
+    fn optional(self) -> Regex {
+        match self {
+            Regex::Plus(inner) => Regex::Star(inner),
+            regex if regex.nullable() => regex,
+            regex => Regex::Optional(Box::new(regex)),
+        }
+    }
+
+    fn nullable(&self) -> bool {
+        match self {
+            Regex::Epsilon | Regex::Star(_) | Regex::Optional(_) => true,
+            Regex::Symbols(_) => false,
+            Regex::Plus(inner) => inner.nullable(),
+            Regex::Concat(left, right) => left.nullable() && right.nullable(),
+            Regex::Union(left, right) => left.nullable() || right.nullable(),
+        }
+    }
+
+    fn alternatives(self) -> Vec<Regex> {
+        match self {
+            Regex::Union(left, right) => {
+                let mut alternatives = left.alternatives();
+                alternatives.extend(right.alternatives());
+                alternatives
+            }
+            regex => vec![regex],
+        }
+    }
+
+    /// Writes `self` inside an expression of the given precedence: 0 for a
+    /// union, 1 for a concatenation and 2 for a postfix operator. With
+    /// `names`, symbols are spelled by it and set apart by spaces, and a class
+    /// is written as a union.
+    fn write(&self, f: &mut fmt::Formatter<'_>, precedence: u8, names: Names) -> fmt::Result {
+        let own = match self {
+            Regex::Union(..) => 0,
+            Regex::Symbols(symbols) if names.is_some() && symbols.len() > 1 => 0,
+            Regex::Concat(..) => 1,
+            _ => 2,
+        };
+        if own < precedence {
+            write!(f, "(")?;
+        }
+        match self {
+            Regex::Epsilon => write!(f, "ε")?,
+            Regex::Symbols(symbols) if symbols.len() == 1 => write_name(f, symbols[0], names)?,
+            Regex::Symbols(symbols) if names.is_some() => {
+                for (i, a) in symbols.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "|")?;
+                    }
+                    write_name(f, *a, names)?;
+                }
+            }
+            Regex::Symbols(symbols) => {
+                write!(f, "[")?;
+                let mut i = 0;
+                while i < symbols.len() {
+                    let mut j = i;
+                    while j + 1 < symbols.len() && symbols[j + 1] as u32 == symbols[j] as u32 + 1 {
+                        j += 1;
+                    }
+                    write_symbol(f, symbols[i], true)?;
+                    if j >= i + 2 {
+                        write!(f, "-")?;
+                        write_symbol(f, symbols[j], true)?;
+                        i = j + 1;
+                    } else {
+                        i += 1;
+                    }
+                }
+                write!(f, "]")?;
+            }
+            Regex::Concat(left, right) => {
+                left.write(f, 1, names)?;
+                if names.is_some() {
+                    write!(f, " ")?;
+                }
+                right.write(f, 1, names)?;
+            }
+            Regex::Union(left, right) => {
+                left.write(f, 0, names)?;
+                write!(f, "|")?;
+                right.write(f, 0, names)?;
+            }
+            Regex::Star(inner) => {
+                inner.write(f, 2, names)?;
+                write!(f, "*")?;
+            }
+            Regex::Plus(inner) => {
+                inner.write(f, 2, names)?;
+                write!(f, "+")?;
+            }
+            Regex::Optional(inner) => {
+                inner.write(f, 2, names)?;
+                write!(f, "?")?;
+            }
+        }
+        if own < precedence {
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+/// Spelling of the symbols of a regular expression, see [`Regex::write`].
+type Names<'a> = Option<&'a dyn Fn(char) -> String>;
+
+/// Writes a symbol, a word spelled by `names` escaping what would end it.
+fn write_name(f: &mut fmt::Formatter<'_>, c: char, names: Names) -> fmt::Result {
+    match names.map(|name| name(c)) {
+        Some(name) => {
+            // a lone ε would be the empty input
+            if name == "ε" {
+                write!(f, "\\")?;
+            }
+            for c in name.chars() {
+                if "()|*+?\\".contains(c) || c.is_whitespace() {
+                    write!(f, "\\")?;
+                }
+                write!(f, "{}", c)?;
+            }
+            Ok(())
+        }
+        None => write_symbol(f, c, false),
+    }
+}
+
+fn write_symbol(f: &mut fmt::Formatter<'_>, c: char, in_class: bool) -> fmt::Result {
+    let special = if in_class { "]\\-" } else { "()[]|*+?\\/ε" };
+    if special.contains(c) {
+        write!(f, "\\")?;
+    }
+    write!(f, "{}", c)
+}
+
+/// Prints in the syntax read by [`FiniteAutomata::from_regex`].
+impl fmt::Display for Regex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, 0, None)
+    }
+}
+
+/// Regular expression printed with its symbols spelled by a function, like
+/// the tokens of a [`super::SymbolTable`].
+pub(super) struct Named<'a>(pub(super) &'a Regex, pub(super) &'a dyn Fn(char) -> String);
+
+impl fmt::Display for Named<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.write(f, 0, Some(self.1))
+    }
+}
+
+fn parse_union(chars: &mut Peekable<Chars>) -> Option<Regex> {
+    let mut regex = parse_concat(chars)?;
+    while chars.peek() == Some(&'|') {
+        chars.next();
+        regex = Regex::Union(Box::new(regex), Box::new(parse_concat(chars)?));
+    }
+    Some(regex)
+}
+
+fn parse_concat(chars: &mut Peekable<Chars>) -> Option<Regex> {
+    let mut regex = None;
+    while let Some(c) = chars.peek() {
+        if *c == '|' || *c == ')' {
+            break;
+        }
+        let next = parse_postfix(chars)?;
+        regex = Some(match regex {
+            None => next,
+            Some(regex) => Regex::Concat(Box::new(regex), Box::new(next)),
+        });
+    }
+    Some(regex.unwrap_or(Regex::Epsilon))
+}
+
+fn parse_postfix(chars: &mut Peekable<Chars>) -> Option<Regex> {
+    let mut regex = parse_atom(chars)?;
+    while let Some(c) = chars.peek() {
+        regex = match c {
+            '*' => Regex::Star(Box::new(regex)),
+            '+' => Regex::Plus(Box::new(regex)),
+            '?' => Regex::Optional(Box::new(regex)),
+            _ => break,
+        };
+        chars.next();
+    }
+    Some(regex)
+}
+
+fn parse_atom(chars: &mut Peekable<Chars>) -> Option<Regex> {
+    match chars.next()? {
+        '(' => {
+            let regex = parse_union(chars)?;
+            if chars.next()? != ')' {
+                return None;
+            }
+            Some(regex)
+        }
+        '[' => {
+            let mut symbols = Vec::new();
+            loop {
+                let c = match chars.next()? {
+                    ']' => break,
+                    '\\' => chars.next()?,
+                    c => c,
+                };
+                if chars.peek() == Some(&'-') {
+                    chars.next();
+                    let to = chars.next()?;
+                    if to < c {
+                        return None;
+                    }
+                    symbols.extend(c..=to);
+                } else {
+                    symbols.push(c);
+                }
+            }
+            if symbols.is_empty() {
+                return None;
+            }
+            Some(Regex::Symbols(symbols))
+        }
+        '\\' => match chars.next()? {
+            c @ ('d' | 'w') => symbol_class(&format!("\\{}", c)).map(Regex::Symbols),
+            c => Some(Regex::Symbols(vec![c])),
+        },
+        'ε' => Some(Regex::Epsilon),
+        '*' | '+' | '?' | ')' | ']' | '|' => None,
+        c => Some(Regex::Symbols(vec![c])),
+    }
+}
+
+/// Moves of the automata being built, `None` standing for ε.
+#[derive(Default)]
+struct Thompson {
+    len: usize,
+    moves: Vec<(usize, Option<char>, usize)>,
+}
+
+impl Thompson {
+    fn state(&mut self) -> usize {
+        self.len += 1;
+        self.len - 1
+    }
+
+    /// Adds the fragment of `regex` and returns its start and end states.
+    fn build(&mut self, regex: &Regex) -> (usize, usize) {
+        match regex {
+            Regex::Epsilon => {
+                let (start, end) = (self.state(), self.state());
+                self.moves.push((start, None, end));
+                (start, end)
+            }
+            Regex::Symbols(symbols) => {
+                let (start, end) = (self.state(), self.state());
+                for a in symbols {
+                    self.moves.push((start, Some(*a), end));
+                }
+                (start, end)
+            }
+            Regex::Concat(left, right) => {
+                let (start, left_end) = self.build(left);
+                let (right_start, end) = self.build(right);
+                self.moves.push((left_end, None, right_start));
+                (start, end)
+            }
+            Regex::Union(left, right) => {
+                let start = self.state();
+                let (left_start, left_end) = self.build(left);
+                let (right_start, right_end) = self.build(right);
+                let end = self.state();
+                self.moves.push((start, None, left_start));
+                self.moves.push((start, None, right_start));
+                self.moves.push((left_end, None, end));
+                self.moves.push((right_end, None, end));
+                (start, end)
+            }
+            Regex::Star(inner) | Regex::Plus(inner) | Regex::Optional(inner) => {
+                let start = self.state();
+                let (inner_start, inner_end) = self.build(inner);
+                let end = self.state();
+                self.moves.push((start, None, inner_start));
+                self.moves.push((inner_end, None, end));
+                if !matches!(regex, Regex::Plus(_)) {
+                    self.moves.push((start, None, end));
+                }
+                if !matches!(regex, Regex::Optional(_)) {
+                    self.moves.push((inner_end, None, inner_start));
+                }
+                (start, end)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod regex_tests {
+    use super::{FiniteAutomata, NondeterministicAutomata};
+    use crate::automata::SymbolTable;
+
+    #[test]
+    fn basic() {
+        let fa = FiniteAutomata::from_regex("(0|1)*1").unwrap();
+        assert_eq!(vec!["q0", "q1"], fa.states);
+        let words: Vec<String> = fa.accepted_words(usize::MAX).take(4).collect();
+        assert_eq!(vec!["1", "01", "11", "001"], words);
+    }
+
+    #[test]
+    fn operators() {
+        let fa = FiniteAutomata::from_regex("a+b?[0-2]\\d|()").unwrap();
+        for input in ["", "a00", "aab19", "ab29"] {
+            assert_eq!(Some(true), fa.exec(input.chars()), "{}", input);
+        }
+        for input in ["a", "b00", "a30", "abb00"] {
+            assert_eq!(Some(false), fa.exec(input.chars()), "{}", input);
+        }
+        let nfa = NondeterministicAutomata::from_regex("a+b?[0-2]\\d|()").unwrap();
+        assert!(nfa.exec("aab19".chars()));
+
+        let fa = FiniteAutomata::from_regex("\\w+").unwrap();
+        assert_eq!(63, fa.alphabets.len());
+        assert_eq!(Some(true), fa.exec("a_Z9".chars()));
+        assert_eq!(Some(false), fa.exec("".chars()));
+        assert_eq!(Some(false), fa.exec("a-b".chars()));
+    }
+
+    #[test]
+    fn words() {
+        let mut symbols = SymbolTable::new();
+        let fa = FiniteAutomata::from_regex_with("login (read | w\\+)* logout", |token| {
+            symbols.symbol(token)
+        })
+        .unwrap();
+        let input = symbols.input("login read w+ read logout", fa.alphabets());
+        assert_eq!(Some(true), fa.exec(input.chars()));
+        let input = symbols.input("login w logout", fa.alphabets());
+        assert_eq!(Some(false), fa.exec(input.chars()));
+
+        // single characters stand for themselves
+        let fa =
+            FiniteAutomata::from_regex_with("0 1* | ε", |token| symbols.symbol(token)).unwrap();
+        for input in ["", "0", "011"] {
+            assert_eq!(Some(true), fa.exec(input.chars()), "{}", input);
+        }
+        assert_eq!(Some(false), fa.exec("01 1".chars()));
+        assert!(FiniteAutomata::from_regex_with("(a", |token| symbols.symbol(token)).is_none());
+    }
+
+    #[test]
+    fn invalid() {
+        for pattern in ["(0|1", "0)", "*1", "[]", "[b-a]", "0|+"] {
+            assert!(FiniteAutomata::from_regex(pattern).is_none(), "{}", pattern);
+        }
+    }
+}
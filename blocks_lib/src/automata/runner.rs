@@ -0,0 +1,160 @@
+use std::rc::Rc;
+
+use super::{FiniteAutomata, SymbolTable};
+
+/// Run of a [`FiniteAutomata`] fed its input a symbol or a chunk at a time,
+/// so it can be checked while it arrives. Cloning it is cheap, a clone goes
+/// on from the same point independently, for instance to try another
+/// continuation. Symbols are chars as in [`FiniteAutomata::exec`], or tokens
+/// looked up in a [`SymbolTable`] with [`Runner::feed_token`].
+#[derive(Debug, Clone)]
+pub struct Runner<'a> {
+    fa: &'a FiniteAutomata,
+    // states from which a final state can be reached
+    live: Rc<[bool]>,
+    // `None` once a symbol had no transition
+    state: Option<usize>,
+    position: usize,
+}
+
+impl FiniteAutomata {
+    /// Runner in the initial state, nothing read yet.
+    pub fn runner(&self) -> Runner<'_> {
+        let mut live = vec![false; self.states.len()];
+        for i in self.coreachable_indices() {
+            live[i] = true;
+        }
+        Runner {
+            fa: self,
+            live: live.into(),
+            state: self.initial_index(),
+            position: 0,
+        }
+    }
+}
+
+impl Runner<'_> {
+    /// Reads one symbol. Once stuck on a missing transition, the runner
+    /// stays stuck whatever comes next.
+    pub fn feed(&mut self, a: char) {
+        self.position += 1;
+        self.state = self.state.and_then(|i| self.fa.next_index(i, a));
+    }
+
+    /// Reads the symbol spelled `token`, like `login`, which `symbols` gave a
+    /// char when the automata was built. An unknown token is outside the
+    /// alphabet, so the runner gets stuck.
+    pub fn feed_token(&mut self, token: &str, symbols: &SymbolTable) {
+        match symbols.get(token) {
+            Some(a) => self.feed(a),
+            None => {
+                self.position += 1;
+                self.state = None;
+            }
+        }
+    }
+
+    pub fn feed_chunk(&mut self, chunk: &str) {
+        for a in chunk.chars() {
+            self.feed(a);
+        }
+    }
+
+    /// Current state, `None` when stuck.
+    pub fn state(&self) -> Option<&str> {
+        self.state.map(|i| self.fa.states[i].as_str())
+    }
+
+    /// Number of symbols read so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// True when the input read so far is accepted.
+    pub fn is_accepting(&self) -> bool {
+        self.state.is_some_and(|i| self.fa.is_final_index(i))
+    }
+
+    /// True when no continuation of the input read so far can be accepted,
+    /// either because it got stuck or because no final state can be reached
+    /// anymore.
+    pub fn is_dead(&self) -> bool {
+        self.state.is_none_or(|i| !self.live[i])
+    }
+}
+
+#[cfg(test)]
+mod runner_tests {
+    use super::super::fixtures::from_table;
+    use super::SymbolTable;
+
+    #[test]
+    fn basic() {
+        // starts with 1, then anything, ending in 0
+        let fa = from_table(
+            &['0', '1'],
+            &["C"],
+            &[
+                ("A", '1', "B"),
+                ("A", '0', "D"),
+                ("B", '1', "B"),
+                ("B", '0', "C"),
+                ("C", '0', "C"),
+                ("C", '1', "B"),
+                ("D", '0', "D"),
+                ("D", '1', "D"),
+            ],
+        );
+        let mut runner = fa.runner();
+        assert_eq!(Some("A"), runner.state());
+        assert!(!runner.is_accepting() && !runner.is_dead());
+
+        runner.feed_chunk("10");
+        assert_eq!(Some("C"), runner.state());
+        assert!(runner.is_accepting());
+
+        // backtracking from a copy
+        let mut other = runner.clone();
+        other.feed('1');
+        assert!(!other.is_accepting());
+        assert!(runner.is_accepting());
+
+        // D can never accept
+        let mut dead = fa.runner();
+        dead.feed('0');
+        assert_eq!(Some("D"), dead.state());
+        assert!(dead.is_dead());
+    }
+
+    #[test]
+    fn stuck() {
+        let fa = from_table(&['0', '1'], &["B"], &[("A", '0', "B")]);
+        let mut runner = fa.runner();
+        runner.feed_chunk("02");
+        assert_eq!(None, runner.state());
+        assert!(runner.is_dead() && !runner.is_accepting());
+        runner.feed_chunk("0");
+        assert_eq!(3, runner.position());
+        assert_eq!(None, runner.state());
+    }
+
+    #[test]
+    fn tokens() {
+        let mut symbols = SymbolTable::new();
+        let login = symbols.symbol("login");
+        let logout = symbols.symbol("logout");
+        let fa = from_table(
+            &[login, logout],
+            &["A"],
+            &[("A", login, "B"), ("B", logout, "A")],
+        );
+        let mut runner = fa.runner();
+        runner.feed_token("login", &symbols);
+        assert_eq!(Some("B"), runner.state());
+        runner.feed_token("logout", &symbols);
+        assert!(runner.is_accepting());
+        runner.feed_token("read", &symbols);
+        assert_eq!(3, runner.position());
+        assert!(runner.is_dead());
+    }
+}
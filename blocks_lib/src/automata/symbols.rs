@@ -0,0 +1,201 @@
+/// First char handed out to multi-character symbols, the start of the
+/// supplementary private use areas.
+const FIRST: u32 = 0xF0000;
+/// Number of chars from `FIRST` to the last one.
+const CAPACITY: usize = (char::MAX as u32 - FIRST + 1) as usize;
+
+/// Symbols of a range like `a-z`, or of a class: `\d` for the digits and `\w`
+/// for the ASCII letters, the digits and `_`. `None` for anything else.
+pub fn symbol_class(item: &str) -> Option<Vec<char>> {
+    match item {
+        "\\d" => return Some(('0'..='9').collect()),
+        "\\w" => {
+            let word = ('a'..='z').chain('A'..='Z').chain('0'..='9').chain(['_']);
+            return Some(word.collect());
+        }
+        _ => {}
+    }
+    let chars: Vec<char> = item.chars().collect();
+    match chars[..] {
+        [first, '-', last]
+            if first.is_alphanumeric() && last.is_alphanumeric() && first <= last =>
+        {
+            Some((first..=last).collect())
+        }
+        _ => None,
+    }
+}
+
+/// Symbols spelled with several characters, like `login`, for automata that
+/// read chars. Each one stands for a char of the supplementary private use
+/// areas, which are reserved for them, while any other character stands for
+/// itself.
+#[derive(Default, Debug, Clone)]
+pub struct SymbolTable {
+    tokens: Vec<String>,
+}
+
+impl SymbolTable {
+    pub fn new() -> Self {
+        Self {
+            ..Default::default()
+        }
+    }
+
+    /// True when `a` can only stand for a multi-character symbol.
+    pub fn is_reserved(a: char) -> bool {
+        a as u32 >= FIRST
+    }
+
+    /// Char standing for `token`, handing out a new one when it is first seen.
+    pub fn symbol(&mut self, token: &str) -> char {
+        let mut chars = token.chars();
+        if let (Some(a), None) = (chars.next(), chars.next()) {
+            if Self::is_reserved(a) {
+                panic!("{:?} is reserved for multi-character symbols", a);
+            }
+        }
+        match self.get(token) {
+            Some(a) => a,
+            None => self.intern(token),
+        }
+    }
+
+    /// Char standing for `token`, `None` when it has not been seen and is not
+    /// a single character standing for itself.
+    pub fn get(&self, token: &str) -> Option<char> {
+        let mut chars = token.chars();
+        match (chars.next(), chars.next()) {
+            (Some(a), None) if !Self::is_reserved(a) => Some(a),
+            _ => {
+                let i = self.tokens.iter().position(|t| t == token)?;
+                char::from_u32(FIRST + i as u32)
+            }
+        }
+    }
+
+    /// Token `a` stands for.
+    pub fn token(&self, a: char) -> String {
+        match self.index(a) {
+            Some(i) => self.tokens[i].clone(),
+            None => a.to_string(),
+        }
+    }
+
+    /// Chars standing for an input of a block reading `alphabet`. When the
+    /// alphabet has multi-character symbols, the input is a sequence of tokens
+    /// separated by whitespace or commas, like `login, read logout`,
+    /// otherwise it has a symbol per character. Tokens and reserved
+    /// characters that are not symbols get new chars, so they end up outside
+    /// every alphabet.
+    pub fn input(&mut self, input: &str, alphabet: &[char]) -> String {
+        if !alphabet.iter().any(|a| self.index(*a).is_some()) {
+            return input
+                .chars()
+                .map(|a| {
+                    if Self::is_reserved(a) {
+                        self.intern(&a.to_string())
+                    } else {
+                        a
+                    }
+                })
+                .collect();
+        }
+        input
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|token| !token.is_empty())
+            .map(|token| match self.get(token) {
+                Some(a) => a,
+                None => self.intern(token),
+            })
+            .collect()
+    }
+
+    /// Inverse of [`SymbolTable::input`], the tokens of `word` are separated
+    /// by spaces when one of them has several characters.
+    pub fn word(&self, word: &str) -> String {
+        if word.chars().all(|a| self.index(a).is_none()) {
+            return word.to_owned();
+        }
+        let tokens: Vec<String> = word.chars().map(|a| self.token(a)).collect();
+        tokens.join(" ")
+    }
+
+    /// `text` with each char standing for a token replaced by it, for
+    /// messages where symbols are already set apart, like `A = login -> B`.
+    pub fn text(&self, text: &str) -> String {
+        text.chars().map(|a| self.token(a)).collect()
+    }
+
+    fn intern(&mut self, token: &str) -> char {
+        if self.tokens.len() == CAPACITY {
+            panic!("Too many multi-character symbols");
+        }
+        self.tokens.push(token.to_owned());
+        char::from_u32(FIRST + self.tokens.len() as u32 - 1).unwrap()
+    }
+
+    fn index(&self, a: char) -> Option<usize> {
+        let i = (a as u32).checked_sub(FIRST)? as usize;
+        (i < self.tokens.len()).then_some(i)
+    }
+}
+
+#[cfg(test)]
+mod symbols_tests {
+    use super::{symbol_class, SymbolTable};
+
+    #[test]
+    fn basic() {
+        let mut table = SymbolTable::new();
+        assert_eq!('a', table.symbol("a"));
+        let login = table.symbol("login");
+        let read = table.symbol("read");
+        assert_ne!(login, read);
+        assert_eq!(login, table.symbol("login"));
+        assert_eq!("read", table.token(read));
+
+        let alphabet = [login, read];
+        let input = table.input("login,read  read", &alphabet);
+        assert_eq!(vec![login, read, read], input.chars().collect::<Vec<_>>());
+        assert_eq!("login read read", table.word(&input));
+        assert_eq!(read.to_string(), table.input("read", &alphabet));
+        assert_eq!("011", table.word("011"));
+
+        assert_eq!(Some(login), table.get("login"));
+        assert_eq!(Some('0'), table.get("0"));
+        assert_eq!(None, table.get("logout"));
+        assert_eq!(None, table.get(&char::MAX.to_string()));
+    }
+
+    #[test]
+    fn characters() {
+        // blocks without multi-character symbols read characters
+        let mut table = SymbolTable::new();
+        let read = table.symbol("read");
+        assert_eq!("read", table.input("read", &['r', 'e', 'a', 'd']));
+        assert_eq!("0 1", table.input("0 1", &['0', '1']));
+
+        // unknown tokens and reserved characters are none of the symbols
+        let typo = table.input("raed", &[read]);
+        assert_ne!(read.to_string(), typo);
+        assert_eq!("raed", table.text(&typo));
+        let reserved = table.input(&read.to_string(), &['0']);
+        assert_ne!(read.to_string(), reserved);
+        assert!(SymbolTable::is_reserved(read));
+    }
+
+    #[test]
+    fn classes() {
+        assert_eq!(Some(vec!['x', 'y', 'z']), symbol_class("x-z"));
+        assert_eq!(10, symbol_class("\\d").unwrap().len());
+        assert_eq!(63, symbol_class("\\w").unwrap().len());
+        assert_eq!(None, symbol_class("ab"));
+    }
+
+    #[test]
+    #[should_panic(expected = "is reserved for multi-character symbols")]
+    fn reserved() {
+        SymbolTable::new().symbol("\u{F0000}");
+    }
+}
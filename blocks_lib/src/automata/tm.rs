@@ -0,0 +1,293 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
+    str::Chars,
+};
+
+use super::DEFAULT_STEP_LIMIT;
+
+/// Where the head goes after writing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Move {
+    Left,
+    Right,
+    Stay,
+}
+
+/// Why a run of a Turing machine stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Halt {
+    /// A final state was entered.
+    Accept,
+    /// No transition for the current state and symbol.
+    Reject,
+    /// The step limit was reached while still running.
+    StepLimit,
+}
+
+/// State, tape and head position of a Turing machine at some step. The tape
+/// only holds the cells visited so far.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Configuration {
+    pub state: String,
+    pub tape: Vec<char>,
+    pub head: usize,
+}
+
+/// Outcome of a run with the tape it was left with, without the blanks on
+/// either end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TuringRun {
+    pub halt: Halt,
+    pub tape: String,
+    pub steps: usize,
+}
+
+/// Deterministic single tape Turing machine. The input is written on a tape
+/// that is blank everywhere else, and the head starts on its first symbol.
+#[derive(Debug, Clone)]
+pub struct TuringMachine {
+    alphabets: Vec<char>,
+    tape_alphabets: Vec<char>,
+    blank: char,
+    states: Vec<String>,
+    initial_state: String,
+    final_states: HashSet<String>,
+    step_limit: usize,
+    delta: HashMap<(String, char), (String, char, Move)>,
+}
+
+impl Default for TuringMachine {
+    fn default() -> Self {
+        Self {
+            alphabets: Vec::new(),
+            tape_alphabets: Vec::new(),
+            blank: '_',
+            states: Vec::new(),
+            initial_state: String::new(),
+            final_states: HashSet::new(),
+            step_limit: DEFAULT_STEP_LIMIT,
+            delta: HashMap::new(),
+        }
+    }
+}
+
+impl TuringMachine {
+    pub fn new() -> Self {
+        Self {
+            ..Default::default()
+        }
+    }
+
+    /// Input alphabet, its symbols are also tape symbols.
+    pub fn add_alphabets(&mut self, alpha: &[char]) {
+        self.alphabets = Vec::from(alpha);
+    }
+
+    /// Symbols that can be written on the tape besides the input ones.
+    pub fn add_tape_alphabets(&mut self, alpha: &[char]) {
+        self.tape_alphabets = Vec::from(alpha);
+    }
+
+    pub fn blank(&mut self, blank: char) {
+        self.blank = blank;
+    }
+
+    pub fn add_states(&mut self, states: &[String]) {
+        self.states = Vec::from(states);
+    }
+
+    pub fn initial_state(&mut self, state: String) {
+        self.initial_state = state;
+    }
+
+    pub fn final_state(&mut self, state: String) {
+        self.final_states.insert(state);
+    }
+
+    pub fn final_states(&mut self, states: &[String]) {
+        self.final_states = states.iter().cloned().collect();
+    }
+
+    /// Bound on the number of transitions a run may take.
+    pub fn step_limit(&mut self, limit: usize) {
+        self.step_limit = limit;
+    }
+
+    /// On every symbol of `read` in `state1`, writes `write`, moves the head
+    /// and goes to `state2`. Undeclared states or symbols are ignored.
+    pub fn add_action(
+        &mut self,
+        state1: String,
+        read: &[char],
+        state2: String,
+        write: char,
+        direction: Move,
+    ) {
+        if !self.states.contains(&state1)
+            || !self.states.contains(&state2)
+            || read.iter().any(|a| !self.is_tape_symbol(*a))
+            || !self.is_tape_symbol(write)
+        {
+            return;
+        }
+        for a in read {
+            self.delta
+                .insert((state1.clone(), *a), (state2.clone(), write, direction));
+        }
+    }
+
+    pub fn exec(&self, input: Chars) -> TuringRun {
+        self.run(input, |_| {})
+    }
+
+    /// Same as [`TuringMachine::exec`], also returning the configuration
+    /// before every step and the last one.
+    pub fn exec_trace(&self, input: Chars) -> (TuringRun, Vec<Configuration>) {
+        let mut trace = Vec::new();
+        let run = self.run(input, |config| trace.push(config));
+        (run, trace)
+    }
+
+    pub fn states(&self) -> &[String] {
+        &self.states
+    }
+
+    pub fn alphabets(&self) -> &[char] {
+        &self.alphabets
+    }
+
+    fn is_tape_symbol(&self, a: char) -> bool {
+        a == self.blank || self.alphabets.contains(&a) || self.tape_alphabets.contains(&a)
+    }
+
+    fn run(&self, input: Chars, mut visit: impl FnMut(Configuration)) -> TuringRun {
+        let mut tape: VecDeque<char> = input.collect();
+        if tape.is_empty() {
+            tape.push_back(self.blank);
+        }
+        let mut head = 0;
+        let mut state = &self.initial_state;
+        let mut steps = 0;
+        let halt = loop {
+            visit(Configuration {
+                state: state.clone(),
+                tape: tape.iter().copied().collect(),
+                head,
+            });
+            if self.final_states.contains(state) {
+                break Halt::Accept;
+            }
+            let (next, write, direction) = match self.delta.get(&(state.clone(), tape[head])) {
+                Some(action) => action,
+                None => break Halt::Reject,
+            };
+            if steps == self.step_limit {
+                break Halt::StepLimit;
+            }
+            steps += 1;
+            tape[head] = *write;
+            match direction {
+                Move::Left if head == 0 => tape.push_front(self.blank),
+                Move::Left => head -= 1,
+                Move::Right => {
+                    head += 1;
+                    if head == tape.len() {
+                        tape.push_back(self.blank);
+                    }
+                }
+                Move::Stay => {}
+            }
+            state = next;
+        };
+        let tape: String = tape.into_iter().collect();
+        TuringRun {
+            halt,
+            tape: tape.trim_matches(self.blank).to_string(),
+            steps,
+        }
+    }
+}
+
+impl fmt::Display for Halt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Halt::Accept => write!(f, "accept"),
+            Halt::Reject => write!(f, "reject"),
+            Halt::StepLimit => write!(f, "step limit"),
+        }
+    }
+}
+
+/// Shown like `A 01[1]0`, the head being on the bracketed symbol.
+impl fmt::Display for Configuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ", self.state)?;
+        for (i, a) in self.tape.iter().enumerate() {
+            if i == self.head {
+                write!(f, "[{}]", a)?;
+            } else {
+                write!(f, "{}", a)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tm_tests {
+    use super::{Halt, Move, TuringMachine};
+
+    // adds one to a binary number, the head going to the last digit first
+    fn increment() -> TuringMachine {
+        let mut tm = TuringMachine::new();
+        tm.add_alphabets(&['0', '1']);
+        tm.add_states(&["R".to_string(), "C".to_string(), "H".to_string()]);
+        tm.initial_state("R".to_string());
+        tm.final_state("H".to_string());
+        for (state1, read, state2, write, direction) in [
+            ("R", '0', "R", '0', Move::Right),
+            ("R", '1', "R", '1', Move::Right),
+            ("R", '_', "C", '_', Move::Left),
+            ("C", '1', "C", '0', Move::Left),
+            ("C", '0', "H", '1', Move::Stay),
+            ("C", '_', "H", '1', Move::Stay),
+        ] {
+            tm.add_action(
+                state1.to_string(),
+                &[read],
+                state2.to_string(),
+                write,
+                direction,
+            );
+        }
+        tm
+    }
+
+    #[test]
+    fn basic() {
+        let mut tm = increment();
+        let run = tm.exec("1011".chars());
+        assert_eq!(Halt::Accept, run.halt);
+        assert_eq!("1100", run.tape);
+        assert_eq!("100", tm.exec("11".chars()).tape);
+        assert_eq!("1", tm.exec("".chars()).tape);
+        assert_eq!(Halt::Reject, tm.exec("12".chars()).halt);
+
+        tm.step_limit(3);
+        let run = tm.exec("1011".chars());
+        assert_eq!((Halt::StepLimit, 3), (run.halt, run.steps));
+    }
+
+    #[test]
+    fn trace() {
+        let tm = increment();
+        let (run, trace) = tm.exec_trace("1".chars());
+        let trace: Vec<String> = trace.iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            vec!["R [1]", "R 1[_]", "C [1]_", "C [_]0_", "H [1]0_"],
+            trace
+        );
+        assert_eq!(run.steps + 1, trace.len());
+    }
+}
@@ -0,0 +1,121 @@
+use std::{fmt, str::Chars};
+
+use super::FiniteAutomata;
+
+/// Transition taken while running an automata. A multi-character symbol is
+/// the char a [`SymbolTable`](super::SymbolTable) gave it, spelled back by
+/// [`SymbolTable::token`](super::SymbolTable::token).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub state: String,
+    pub symbol: char,
+    pub next: String,
+}
+
+/// Why a run of a [`FiniteAutomata`] accepted or rejected its input.
+/// Positions count the symbols read before the faulty one, and symbols are
+/// chars as in [`Step`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Accepted,
+    /// The whole input was read, ending in a state that is not final.
+    Rejected,
+    /// The state reached has no transition on the symbol at `position`.
+    Stuck {
+        position: usize,
+        symbol: char,
+    },
+    /// The symbol at `position` is not in the alphabet.
+    UnknownSymbol {
+        position: usize,
+        symbol: char,
+    },
+}
+
+impl FiniteAutomata {
+    /// Same as [`FiniteAutomata::exec`], also returning the transitions
+    /// taken and why the run ended.
+    pub fn exec_trace(&self, input: Chars) -> (Outcome, Vec<Step>) {
+        let mut steps = Vec::new();
+        let mut state = self.initial_index();
+        for (position, symbol) in input.enumerate() {
+            if self.symbol_index(symbol).is_none() {
+                return (Outcome::UnknownSymbol { position, symbol }, steps);
+            }
+            let next = state.and_then(|i| self.next_index(i, symbol));
+            let (i, j) = match (state, next) {
+                (Some(i), Some(j)) => (i, j),
+                _ => return (Outcome::Stuck { position, symbol }, steps),
+            };
+            steps.push(Step {
+                state: self.states[i].clone(),
+                symbol,
+                next: self.states[j].clone(),
+            });
+            state = Some(j);
+        }
+        if state.is_some_and(|i| self.is_final_index(i)) {
+            (Outcome::Accepted, steps)
+        } else {
+            (Outcome::Rejected, steps)
+        }
+    }
+}
+
+/// Shown like a transition, `A = 0 -> B`.
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = {} -> {}", self.state, self.symbol, self.next)
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Accepted => write!(f, "accepted"),
+            Outcome::Rejected => write!(f, "rejected"),
+            Outcome::Stuck { position, symbol } => {
+                write!(f, "stuck at {}, no transition on {}", position, symbol)
+            }
+            Outcome::UnknownSymbol { position, symbol } => {
+                write!(
+                    f,
+                    "stuck at {}, {} is not in the alphabet",
+                    position, symbol
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod trace_tests {
+    use super::super::fixtures::from_table;
+    use super::Outcome;
+
+    #[test]
+    fn basic() {
+        let fa = from_table(
+            &['0', '1'],
+            &["B"],
+            &[("A", '0', "A"), ("A", '1', "B"), ("B", '1', "B")],
+        );
+        let (outcome, steps) = fa.exec_trace("011".chars());
+        assert_eq!(Outcome::Accepted, outcome);
+        let steps: Vec<String> = steps.iter().map(|s| s.to_string()).collect();
+        assert_eq!(vec!["A = 0 -> A", "A = 1 -> B", "B = 1 -> B"], steps);
+
+        assert_eq!(Outcome::Rejected, fa.exec_trace("00".chars()).0);
+        let (outcome, steps) = fa.exec_trace("0110".chars());
+        let stuck = Outcome::Stuck {
+            position: 3,
+            symbol: '0',
+        };
+        assert_eq!((stuck, 3), (outcome, steps.len()));
+        let unknown = Outcome::UnknownSymbol {
+            position: 1,
+            symbol: '2',
+        };
+        assert_eq!(unknown, fa.exec_trace("02".chars()).0);
+    }
+}
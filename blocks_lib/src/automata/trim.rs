@@ -0,0 +1,99 @@
+use super::FiniteAutomata;
+
+impl FiniteAutomata {
+    /// States that can be reached from the initial state, in declaration
+    /// order.
+    pub fn reachable_states(&self) -> Vec<String> {
+        self.names(self.reachable_indices())
+    }
+
+    /// States from which a final state can be reached, in declaration order.
+    pub fn coreachable_states(&self) -> Vec<String> {
+        self.names(self.coreachable_indices())
+    }
+
+    pub fn trim(&self) -> FiniteAutomata {
+        self.trim_with_report().0
+    }
+
+    /// Removes the states that are unreachable or can never reach a final
+    /// state, keeping the names of the others, and reports the removed ones
+    /// in declaration order. The initial state is always kept so the result
+    /// can still be run, even when it accepts nothing.
+    pub fn trim_with_report(&self) -> (FiniteAutomata, Vec<String>) {
+        let mut live = vec![false; self.states.len()];
+        for i in self.coreachable_indices() {
+            live[i] = true;
+        }
+        let mut keep = vec![false; self.states.len()];
+        for i in self.reachable_indices() {
+            keep[i] = live[i];
+        }
+        if let Some(i) = self.initial_index() {
+            keep[i] = true;
+        }
+
+        let (kept, removed): (Vec<usize>, Vec<usize>) =
+            (0..self.states.len()).partition(|i| keep[*i]);
+        let mut fa = FiniteAutomata::new();
+        fa.rejects_unknown = self.rejects_unknown;
+        fa.add_alphabets(&self.alphabets);
+        fa.add_states(&self.names(kept.clone()));
+        if let Some(i) = self.initial_index() {
+            fa.initial_state(self.states[i].clone());
+        }
+        for i in kept {
+            if self.is_final_index(i) {
+                fa.final_state(self.states[i].clone());
+            }
+            for a in &self.alphabets {
+                match self.next_index(i, *a) {
+                    Some(j) if keep[j] => {
+                        fa.add_action(self.states[i].clone(), &[*a], self.states[j].clone())
+                    }
+                    _ => {}
+                }
+            }
+        }
+        (fa, self.names(removed))
+    }
+
+    fn names(&self, indices: Vec<usize>) -> Vec<String> {
+        indices
+            .into_iter()
+            .map(|i| self.states[i].clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod trim_tests {
+    use super::super::fixtures::{from_table, useless_states};
+
+    #[test]
+    fn basic() {
+        let fa = useless_states();
+        assert_eq!(vec!["A", "B", "C"], fa.reachable_states());
+        assert_eq!(vec!["A", "B", "D"], fa.coreachable_states());
+
+        let (trimmed, removed) = fa.trim_with_report();
+        assert_eq!(vec!["C", "D"], removed);
+        assert_eq!(vec!["A", "B"], trimmed.states());
+        for input in ["0", "000", "1", "10", "01", ""] {
+            assert_eq!(
+                fa.exec(input.chars()).unwrap_or(false),
+                trimmed.exec(input.chars()).unwrap_or(false)
+            );
+        }
+    }
+
+    #[test]
+    fn empty() {
+        let fa = from_table(&['0'], &[], &[("A", '0', "B"), ("B", '0', "A")]);
+        let (trimmed, removed) = fa.trim_with_report();
+        assert_eq!(vec!["B"], removed);
+        assert_eq!(vec!["A"], trimmed.states());
+        assert!(trimmed.is_empty());
+        assert_eq!(Some(false), trimmed.exec("".chars()));
+    }
+}
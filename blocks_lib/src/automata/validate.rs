@@ -0,0 +1,197 @@
+use std::{collections::HashSet, fmt};
+
+use super::{FiniteAutomata, NondeterministicAutomata};
+
+/// Something suspicious about an automata or the way it was declared.
+/// Symbols are spelled out, so a multi-character symbol can be given as its
+/// token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    /// A transition names a state that was not declared.
+    UndeclaredState(String),
+    /// A transition reads a symbol that is not in the alphabet.
+    UnknownSymbol(String),
+    /// The same transition is declared again.
+    Duplicate { state: String, symbol: String },
+    /// A deterministic transition is declared again with another next
+    /// state, which replaces the first one.
+    Conflict {
+        state: String,
+        symbol: String,
+        next: String,
+    },
+    /// The state cannot be reached from the initial state.
+    Unreachable(String),
+    /// No final state can be reached from the state.
+    Dead(String),
+    /// The state has no transition on the symbol.
+    Missing { state: String, symbol: String },
+}
+
+impl Issue {
+    /// Errors make the automata differ from what was written, the other
+    /// issues are warnings.
+    pub fn is_error(&self) -> bool {
+        matches!(
+            self,
+            Issue::UndeclaredState(_) | Issue::UnknownSymbol(_) | Issue::Conflict { .. }
+        )
+    }
+}
+
+impl FiniteAutomata {
+    /// Unreachable and dead states, then missing transitions, in declaration
+    /// order. Missing transitions of dead states are not reported, since
+    /// going there rejects anyway.
+    pub fn validate(&self) -> Vec<Issue> {
+        let mut reachable = vec![false; self.states.len()];
+        for i in self.reachable_indices() {
+            reachable[i] = true;
+        }
+        let mut live = vec![false; self.states.len()];
+        for i in self.coreachable_indices() {
+            live[i] = true;
+        }
+        report(&self.states, &self.alphabets, &reachable, &live, |i, a| {
+            self.next_index(i, a).is_none()
+        })
+    }
+}
+
+impl NondeterministicAutomata {
+    /// Same as [`FiniteAutomata::validate`], a state missing a transition on a
+    /// symbol when neither it nor the states its ε-moves reach have one.
+    pub fn validate(&self) -> Vec<Issue> {
+        let reachable: HashSet<String> = self.reachable_states().into_iter().collect();
+        let live: HashSet<String> = self.coreachable_states().into_iter().collect();
+        let states = self.states();
+        let reachable: Vec<bool> = states.iter().map(|s| reachable.contains(s)).collect();
+        let live: Vec<bool> = states.iter().map(|s| live.contains(s)).collect();
+        let closures: Vec<HashSet<String>> = states
+            .iter()
+            .map(|s| self.epsilon_closure(&HashSet::from([s.clone()])))
+            .collect();
+        report(states, self.alphabets(), &reachable, &live, |i, a| {
+            self.step(&closures[i], a).is_empty()
+        })
+    }
+}
+
+/// Unreachable and dead states, then missing transitions of the live ones,
+/// in declaration order.
+fn report(
+    states: &[String],
+    alphabets: &[char],
+    reachable: &[bool],
+    live: &[bool],
+    is_missing: impl Fn(usize, char) -> bool,
+) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    for (i, state) in states.iter().enumerate() {
+        if !reachable[i] {
+            issues.push(Issue::Unreachable(state.clone()));
+        }
+    }
+    for (i, state) in states.iter().enumerate() {
+        if !live[i] {
+            issues.push(Issue::Dead(state.clone()));
+        }
+    }
+    for (i, state) in states.iter().enumerate() {
+        if !live[i] {
+            continue;
+        }
+        for a in alphabets {
+            if is_missing(i, *a) {
+                issues.push(Issue::Missing {
+                    state: state.clone(),
+                    symbol: a.to_string(),
+                });
+            }
+        }
+    }
+    issues
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::UndeclaredState(state) => write!(f, "state {} is not declared", state),
+            Issue::UnknownSymbol(a) => write!(f, "{} is not in the alphabet", a),
+            Issue::Duplicate { state, symbol } => {
+                write!(f, "{} = {} is declared twice", state, symbol)
+            }
+            Issue::Conflict {
+                state,
+                symbol,
+                next,
+            } => write!(
+                f,
+                "{} = {} is declared again going to {}",
+                state, symbol, next
+            ),
+            Issue::Unreachable(state) => write!(f, "{} is unreachable", state),
+            Issue::Dead(state) => write!(f, "{} can never reach a final state", state),
+            Issue::Missing { state, symbol } => {
+                write!(f, "{} has no transition on {}", state, symbol)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod validate_tests {
+    use super::super::fixtures::{nfa_from_table, useless_states};
+    use super::Issue;
+
+    #[test]
+    fn basic() {
+        let fa = useless_states();
+        assert_eq!(
+            vec![
+                Issue::Unreachable("D".to_string()),
+                Issue::Dead("C".to_string()),
+                Issue::Missing {
+                    state: "B".to_string(),
+                    symbol: "1".to_string()
+                },
+                Issue::Missing {
+                    state: "D".to_string(),
+                    symbol: "1".to_string()
+                },
+            ],
+            fa.validate()
+        );
+        assert!(fa.validate().iter().all(|issue| !issue.is_error()));
+    }
+
+    #[test]
+    fn nfa() {
+        // B only reaches the final state through an ε-move, D is unreachable
+        // and C can never accept
+        let nfa = nfa_from_table(
+            &['0', '1'],
+            &["E"],
+            &[
+                ("A", '0', "B"),
+                ("A", '1', "C"),
+                ("B", 'ε', "E"),
+                ("C", '0', "C"),
+                ("D", '0', "E"),
+                ("E", '0', "E"),
+                ("E", '1', "E"),
+            ],
+        );
+        assert_eq!(
+            vec![
+                Issue::Unreachable("D".to_string()),
+                Issue::Dead("C".to_string()),
+                Issue::Missing {
+                    state: "D".to_string(),
+                    symbol: "1".to_string()
+                },
+            ],
+            nfa.validate()
+        );
+    }
+}
@@ -0,0 +1,326 @@
+use std::{collections::HashSet, fmt, str::Chars, str::FromStr};
+
+/// Values weights are taken from. `add` combines the weights of different
+/// paths and `mul` the weights along one path, `zero` being the weight of no
+/// path and `one` the weight of the empty one.
+pub trait Semiring: Clone + PartialEq + fmt::Debug + fmt::Display + FromStr {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn add(&self, other: &Self) -> Self;
+    fn mul(&self, other: &Self) -> Self;
+}
+
+/// Probabilities, the weight of an input is the probability to accept it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Probability(pub f64);
+
+/// Costs, the weight of an input is the cost of its cheapest path, `inf` when
+/// there is none.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tropical(pub f64);
+
+/// The weight of an input is its number of accepting paths, weights being
+/// multiplicities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Counting(pub u64);
+
+impl Semiring for Probability {
+    fn zero() -> Self {
+        Probability(0.0)
+    }
+
+    fn one() -> Self {
+        Probability(1.0)
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        Probability(self.0 + other.0)
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        Probability(self.0 * other.0)
+    }
+}
+
+impl Semiring for Tropical {
+    fn zero() -> Self {
+        Tropical(f64::INFINITY)
+    }
+
+    fn one() -> Self {
+        Tropical(0.0)
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        Tropical(self.0.min(other.0))
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        Tropical(self.0 + other.0)
+    }
+}
+
+impl Semiring for Counting {
+    fn zero() -> Self {
+        Counting(0)
+    }
+
+    fn one() -> Self {
+        Counting(1)
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        Counting(self.0.saturating_add(other.0))
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        Counting(self.0.saturating_mul(other.0))
+    }
+}
+
+/// Automata whose transitions carry a weight. It may be nondeterministic,
+/// the weight of an input adds up the weights of its accepting paths, each
+/// one being the product of the weights along it.
+#[derive(Debug, Clone)]
+pub struct WeightedAutomata<S: Semiring> {
+    alphabets: Vec<char>,
+    states: Vec<String>,
+    initial_states: HashSet<String>,
+    final_states: HashSet<String>,
+    // targets of each state and symbol, by index in `states`
+    delta: Vec<Vec<Vec<(usize, S)>>>,
+}
+
+impl<S: Semiring> Default for WeightedAutomata<S> {
+    fn default() -> Self {
+        Self {
+            alphabets: Vec::new(),
+            states: Vec::new(),
+            initial_states: HashSet::new(),
+            final_states: HashSet::new(),
+            delta: Vec::new(),
+        }
+    }
+}
+
+impl<S: Semiring> WeightedAutomata<S> {
+    pub fn new() -> Self {
+        Self {
+            ..Default::default()
+        }
+    }
+
+    pub fn add_alphabets(&mut self, alpha: &[char]) {
+        self.declare(Vec::from(alpha), self.states.clone());
+    }
+
+    pub fn add_states(&mut self, states: &[String]) {
+        self.declare(self.alphabets.clone(), Vec::from(states));
+    }
+
+    pub fn initial_state(&mut self, state: String) {
+        self.initial_states.insert(state);
+    }
+
+    pub fn initial_states(&mut self, states: &[String]) {
+        self.initial_states = states.iter().cloned().collect();
+    }
+
+    pub fn final_state(&mut self, state: String) {
+        self.final_states.insert(state);
+    }
+
+    pub fn final_states(&mut self, states: &[String]) {
+        self.final_states = states.iter().cloned().collect();
+    }
+
+    /// Moves from `state1` to `state2` on every symbol of `alpha` with
+    /// `weight`. Adding the same move again adds up the weights, as for two
+    /// different paths. Undeclared states or symbols are ignored.
+    pub fn add_action(&mut self, state1: String, alpha: &[char], state2: String, weight: S) {
+        let i = self.states.iter().position(|s| *s == state1);
+        let j = self.states.iter().position(|s| *s == state2);
+        let (i, j) = match (i, j) {
+            (Some(i), Some(j)) => (i, j),
+            _ => return,
+        };
+        for a in alpha {
+            let ai = match self.alphabets.iter().position(|b| b == a) {
+                Some(ai) => ai,
+                None => continue,
+            };
+            let targets = &mut self.delta[i][ai];
+            match targets.iter_mut().find(|(t, _)| *t == j) {
+                Some(target) => target.1 = target.1.add(&weight),
+                None => targets.push((j, weight.clone())),
+            }
+        }
+    }
+
+    /// Weight of `input`, `zero` when it has no accepting path or uses a
+    /// symbol outside the alphabet.
+    pub fn exec(&self, input: Chars) -> S {
+        let mut weights: Vec<S> = self
+            .states
+            .iter()
+            .map(|s| {
+                if self.initial_states.contains(s) {
+                    S::one()
+                } else {
+                    S::zero()
+                }
+            })
+            .collect();
+        for a in input {
+            let ai = match self.alphabets.iter().position(|b| *b == a) {
+                Some(ai) => ai,
+                None => return S::zero(),
+            };
+            let mut next = vec![S::zero(); self.states.len()];
+            for (i, weight) in weights.iter().enumerate() {
+                if *weight == S::zero() {
+                    continue;
+                }
+                for (j, w) in &self.delta[i][ai] {
+                    next[*j] = next[*j].add(&weight.mul(w));
+                }
+            }
+            weights = next;
+        }
+        self.states
+            .iter()
+            .zip(weights)
+            .filter(|(s, _)| self.final_states.contains(*s))
+            .fold(S::zero(), |total, (_, weight)| total.add(&weight))
+    }
+
+    pub fn states(&self) -> &[String] {
+        &self.states
+    }
+
+    pub fn alphabets(&self) -> &[char] {
+        &self.alphabets
+    }
+
+    /// Replaces the states and symbols, keeping the moves between the ones
+    /// still declared.
+    fn declare(&mut self, alphabets: Vec<char>, states: Vec<String>) {
+        let mut delta = vec![vec![Vec::new(); alphabets.len()]; states.len()];
+        for (i, state) in self.states.iter().enumerate() {
+            let k = match states.iter().position(|s| s == state) {
+                Some(k) => k,
+                None => continue,
+            };
+            for (ai, a) in self.alphabets.iter().enumerate() {
+                let b = match alphabets.iter().position(|b| b == a) {
+                    Some(b) => b,
+                    None => continue,
+                };
+                for (j, weight) in &self.delta[i][ai] {
+                    if let Some(l) = states.iter().position(|s| *s == self.states[*j]) {
+                        delta[k][b].push((l, weight.clone()));
+                    }
+                }
+            }
+        }
+        self.alphabets = alphabets;
+        self.states = states;
+        self.delta = delta;
+    }
+}
+
+macro_rules! impl_float {
+    ($($semiring:ident),*) => {$(
+        impl FromStr for $semiring {
+            type Err = std::num::ParseFloatError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                s.parse().map($semiring)
+            }
+        }
+
+        impl fmt::Display for $semiring {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+    )*};
+}
+
+impl_float!(Probability, Tropical);
+
+impl FromStr for Counting {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Counting)
+    }
+}
+
+impl fmt::Display for Counting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod weighted_tests {
+    use super::{Counting, Probability, Semiring, Tropical, WeightedAutomata};
+
+    // from A, 0 goes to A or B and 1 stays in B, B is final
+    fn wfa<S: Semiring>(weights: [&str; 3]) -> WeightedAutomata<S> {
+        let states = vec!["A".to_owned(), "B".to_owned()];
+        let mut wfa = WeightedAutomata::new();
+        wfa.add_alphabets(&['0', '1']);
+        wfa.add_states(&states);
+        wfa.initial_state(states[0].clone());
+        wfa.final_state(states[1].clone());
+        let weight = |i: usize| weights[i].parse().ok().unwrap();
+        wfa.add_action(states[0].clone(), &['0'], states[0].clone(), weight(0));
+        wfa.add_action(states[0].clone(), &['0'], states[1].clone(), weight(1));
+        wfa.add_action(states[1].clone(), &['1'], states[1].clone(), weight(2));
+        wfa
+    }
+
+    #[test]
+    fn probability() {
+        let wfa: WeightedAutomata<Probability> = wfa(["0.5", "0.5", "1"]);
+        assert_eq!(Probability(0.5), wfa.exec("0".chars()));
+        assert_eq!(Probability(0.25), wfa.exec("001".chars()));
+        assert_eq!(Probability(0.0), wfa.exec("10".chars()));
+    }
+
+    #[test]
+    fn tropical() {
+        let wfa: WeightedAutomata<Tropical> = wfa(["1", "3", "2"]);
+        assert_eq!(Tropical(4.0), wfa.exec("00".chars()));
+        assert_eq!(Tropical(6.0), wfa.exec("001".chars()));
+        assert_eq!("inf", wfa.exec("1".chars()).to_string());
+        // the same move again at a lower cost
+        let mut wfa = wfa;
+        wfa.add_action("A".to_owned(), &['0'], "B".to_owned(), Tropical(0.0));
+        assert_eq!(Tropical(1.0), wfa.exec("00".chars()));
+    }
+
+    #[test]
+    fn counting() {
+        let mut wfa: WeightedAutomata<Counting> = wfa(["1", "1", "1"]);
+        // two more ways from A to B on 0
+        wfa.add_action("A".to_owned(), &['0'], "B".to_owned(), Counting(2));
+        assert_eq!(Counting(3), wfa.exec("0".chars()));
+        assert_eq!(Counting(3), wfa.exec("0011".chars()));
+        assert_eq!(Counting(0), wfa.exec("2".chars()));
+    }
+
+    #[test]
+    fn declarations() {
+        // moves added before a symbol and a state are declared are kept
+        let mut wfa: WeightedAutomata<Counting> = wfa(["1", "1", "1"]);
+        wfa.add_alphabets(&['0', '1', '2']);
+        wfa.add_states(&["A".to_string(), "B".to_string(), "C".to_string()]);
+        wfa.add_action("B".to_owned(), &['2'], "C".to_owned(), Counting(3));
+        wfa.final_state("C".to_owned());
+        assert_eq!(Counting(1), wfa.exec("01".chars()));
+        assert_eq!(Counting(3), wfa.exec("012".chars()));
+    }
+}
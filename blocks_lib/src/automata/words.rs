@@ -0,0 +1,160 @@
+use super::FiniteAutomata;
+
+/// Inputs of a language in shortlex order: shorter inputs first,
+/// then alphabetically. Made by [`FiniteAutomata::accepted_words`] and
+/// [`FiniteAutomata::rejected_words`].
+#[derive(Debug, Clone)]
+pub struct Words {
+    fa: FiniteAutomata,
+    symbols: Vec<char>,
+    max_len: usize,
+    // next length to enumerate and the one in progress
+    len: usize,
+    target: usize,
+    // `finish[k][q]` tells if `q` accepts some input of length `k`
+    finish: Vec<Vec<bool>>,
+    // depth first search over the current length, each entry is a state and
+    // the next symbol to try from it
+    stack: Vec<(usize, usize)>,
+    word: Vec<char>,
+}
+
+impl FiniteAutomata {
+    /// Inputs accepted by `self`, at most `max_len` symbols long.
+    pub fn accepted_words(&self, max_len: usize) -> Words {
+        Words::new(self.clone(), max_len)
+    }
+
+    /// Inputs over the alphabet rejected by `self`, at most `max_len` symbols
+    /// long.
+    pub fn rejected_words(&self, max_len: usize) -> Words {
+        Words::new(self.complement(), max_len)
+    }
+}
+
+impl Words {
+    fn new(fa: FiniteAutomata, max_len: usize) -> Self {
+        // a finite language has no input longer than the number of states
+        let max_len = if fa.is_finite() {
+            max_len.min(fa.states.len())
+        } else {
+            max_len
+        };
+        let mut symbols = fa.alphabets.clone();
+        symbols.sort();
+        symbols.dedup();
+        let finish = vec![(0..fa.states.len()).map(|q| fa.is_final_index(q)).collect()];
+        Self {
+            fa,
+            symbols,
+            max_len,
+            len: 0,
+            target: 0,
+            finish,
+            stack: Vec::new(),
+            word: Vec::new(),
+        }
+    }
+
+    fn finishes(&mut self, len: usize, q: usize) -> bool {
+        while self.finish.len() <= len {
+            let last = self.finish.last().unwrap();
+            let row = (0..self.fa.states.len())
+                .map(|q| {
+                    self.symbols
+                        .iter()
+                        .any(|a| self.fa.next_index(q, *a).is_some_and(|j| last[j]))
+                })
+                .collect();
+            self.finish.push(row);
+        }
+        self.finish[len][q]
+    }
+}
+
+impl Iterator for Words {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        loop {
+            if self.stack.is_empty() {
+                if self.len > self.max_len {
+                    return None;
+                }
+                self.target = self.len;
+                self.len += 1;
+                match self.fa.initial_index() {
+                    Some(i) if self.finishes(self.target, i) => self.stack.push((i, 0)),
+                    _ => continue,
+                }
+            }
+
+            let depth = self.word.len();
+            let (q, from) = *self.stack.last().unwrap();
+            if depth == self.target {
+                let word = self.word.iter().collect();
+                self.stack.pop();
+                self.word.pop();
+                return Some(word);
+            }
+            let remaining = self.target - depth - 1;
+            let mut next = None;
+            for ai in from..self.symbols.len() {
+                if let Some(j) = self.fa.next_index(q, self.symbols[ai]) {
+                    if self.finishes(remaining, j) {
+                        next = Some((ai, j));
+                        break;
+                    }
+                }
+            }
+            match next {
+                Some((ai, j)) => {
+                    self.stack.last_mut().unwrap().1 = ai + 1;
+                    self.stack.push((j, 0));
+                    self.word.push(self.symbols[ai]);
+                }
+                None => {
+                    self.stack.pop();
+                    self.word.pop();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod words_tests {
+    use crate::automata::fixtures::from_table;
+
+    #[test]
+    fn accepted() {
+        // ends in 1
+        let last_one = from_table(
+            &['1', '0'],
+            &["B"],
+            &[
+                ("A", '0', "A"),
+                ("A", '1', "B"),
+                ("B", '0', "A"),
+                ("B", '1', "B"),
+            ],
+        );
+        let words: Vec<String> = last_one.accepted_words(usize::MAX).take(7).collect();
+        assert_eq!(vec!["1", "01", "11", "001", "011", "101", "111"], words);
+        assert_eq!(3, last_one.accepted_words(2).count());
+        let words: Vec<String> = last_one.rejected_words(2).collect();
+        assert_eq!(vec!["", "0", "00", "10"], words);
+    }
+
+    #[test]
+    fn finite() {
+        // 0 or 01, with missing transitions
+        let zero_one = from_table(
+            &['0', '1'],
+            &["B", "C"],
+            &[("A", '0', "B"), ("B", '1', "C")],
+        );
+        let words: Vec<String> = zero_one.accepted_words(usize::MAX).collect();
+        assert_eq!(vec!["0", "01"], words);
+    }
+}
@@ -1,49 +1,228 @@
 use core::panic;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::{
-    automata::FiniteAutomata,
+    automata::{
+        equivalent, symbol_class, Acceptance, Counterexample, Counting, FiniteAutomata, Issue,
+        MealyMachine, MooreMachine, Move, NondeterministicAutomata, Probability, PushdownAutomata,
+        Semiring, SymbolTable, Tropical, TuringMachine, WeightedAutomata,
+    },
     parser::{
-        parse,
-        statement::{Statement, Token},
+        parse_lines,
+        statement::{Statement, Token, EPSILON},
         Scope,
     },
 };
 
+#[derive(Debug)]
+pub enum Block {
+    Deterministic(FiniteAutomata),
+    Nondeterministic(NondeterministicAutomata),
+    Mealy(MealyMachine),
+    Moore(MooreMachine),
+    Pushdown(PushdownAutomata),
+    Turing(TuringMachine),
+    Weighted(Weighted),
+}
+
+/// WFA block, by the semiring its weights are taken from.
+#[derive(Debug)]
+pub enum Weighted {
+    Probability(WeightedAutomata<Probability>),
+    Tropical(WeightedAutomata<Tropical>),
+    Counting(WeightedAutomata<Counting>),
+}
+
+/// Evaluates `$body` with `$wfa` bound to the automata of a [`Weighted`],
+/// whatever its semiring.
+macro_rules! each_weighted {
+    ($weighted:expr, $wfa:ident => $body:expr) => {
+        match $weighted {
+            Weighted::Probability($wfa) => $body,
+            Weighted::Tropical($wfa) => $body,
+            Weighted::Counting($wfa) => $body,
+        }
+    };
+}
+
+impl Block {
+    pub fn alphabets(&self) -> &[char] {
+        match self {
+            Block::Deterministic(fa) => fa.alphabets(),
+            Block::Nondeterministic(nfa) => nfa.alphabets(),
+            Block::Mealy(mealy) => mealy.alphabets(),
+            Block::Moore(moore) => moore.alphabets(),
+            Block::Pushdown(pda) => pda.alphabets(),
+            Block::Turing(tm) => tm.alphabets(),
+            Block::Weighted(weighted) => each_weighted!(weighted, wfa => wfa.alphabets()),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct EngineOptions {
+    /// Out statements print `false` instead of panicking when a block has no
+    /// transition for the next symbol.
+    pub reject_undefined: bool,
+    /// Bound on the configurations a PDA block explores on an input and on
+    /// the steps of a TM block, the defaults of [`PushdownAutomata`] and
+    /// [`TuringMachine`] when `None`. A PDA block reaching it prints
+    /// `undecided`.
+    pub step_limit: Option<usize>,
+}
+
+/// Issue found by [`Engine::validate`] in a block, on the line it points at.
+/// The message spells multi-character symbols out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub line: usize,
+    pub block: String,
+    pub issue: Issue,
+    pub message: String,
+}
+
+/// Where the parts of an FA or NFA block are declared.
+#[derive(Debug, Default)]
+struct Source {
+    states_line: usize,
+    transitions: Vec<Declared>,
+}
+
+/// Transition as written, before undeclared states and symbols are dropped.
+#[derive(Debug)]
+struct Declared {
+    line: usize,
+    state: String,
+    symbols: Vec<char>,
+    next: Vec<String>,
+}
+
 pub struct Engine {
-    blocks: HashMap<String, FiniteAutomata>,
+    blocks: HashMap<String, Block>,
+    sources: HashMap<String, Source>,
+    // multi-character symbols of every block
+    symbols: SymbolTable,
     output: Vec<String>,
+    options: EngineOptions,
 }
 
 impl Engine {
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: String) -> Self {
+        Self::from_str_with_options(s, EngineOptions::default())
+    }
+
+    pub fn from_str_with_options(s: String, options: EngineOptions) -> Self {
         let mut engine = Engine {
             blocks: HashMap::new(),
+            sources: HashMap::new(),
+            symbols: SymbolTable::new(),
             output: Vec::new(),
+            options,
         };
-        let statements = parse(s).unwrap();
+        let statements = parse_lines(s).unwrap_or_else(|error| panic!("{}", error));
         let mut scope = Scope::GlobalScope;
         let mut curr_block = None;
-        for (statement, token) in statements.into_iter() {
+        for (line, statement, token) in statements.into_iter() {
             let mut tokens = token.unwrap();
             match scope {
                 Scope::GlobalScope if curr_block.is_none() => {
-                    if statement == Statement::FADef {
+                    if statement == Statement::FADef
+                        || statement == Statement::NFADef
+                        || statement == Statement::MealyDef
+                        || statement == Statement::MooreDef
+                        || statement == Statement::PDADef
+                        || statement == Statement::TMDef
+                        || statement == Statement::WFADef
+                    {
                         scope = Scope::BlockScope;
-                        let fa = FiniteAutomata::new();
+                        let block = match statement {
+                            Statement::FADef => Block::Deterministic(FiniteAutomata::new()),
+                            Statement::NFADef => {
+                                Block::Nondeterministic(NondeterministicAutomata::new())
+                            }
+                            Statement::MealyDef => Block::Mealy(MealyMachine::new()),
+                            Statement::MooreDef => Block::Moore(MooreMachine::new()),
+                            Statement::PDADef => {
+                                let mut pda = PushdownAutomata::new();
+                                if let Some(limit) = engine.options.step_limit {
+                                    pda.step_limit(limit);
+                                }
+                                Block::Pushdown(pda)
+                            }
+                            Statement::WFADef => match &tokens[2] {
+                                Token::Operator(s) if s == "tropical" => {
+                                    Block::Weighted(Weighted::Tropical(WeightedAutomata::new()))
+                                }
+                                Token::Operator(s) if s == "counting" => {
+                                    Block::Weighted(Weighted::Counting(WeightedAutomata::new()))
+                                }
+                                _ => {
+                                    Block::Weighted(Weighted::Probability(WeightedAutomata::new()))
+                                }
+                            },
+                            _ => {
+                                let mut tm = TuringMachine::new();
+                                if let Some(limit) = engine.options.step_limit {
+                                    tm.step_limit(limit);
+                                }
+                                Block::Turing(tm)
+                            }
+                        };
                         if let Token::Operand(name) = &tokens[1] {
                             curr_block = Some(name.to_owned());
-                            engine.blocks.insert(name.to_owned(), fa);
+                            if matches!(block, Block::Deterministic(_) | Block::Nondeterministic(_))
+                            {
+                                engine.sources.insert(name.to_owned(), Source::default());
+                            }
+                            engine.blocks.insert(name.to_owned(), block);
+                        }
+                    } else if statement == Statement::DerivedDef {
+                        if let Token::Operand(name) = &tokens[1] {
+                            let fa = engine.derive(&tokens[3..]);
+                            engine
+                                .blocks
+                                .insert(name.to_owned(), Block::Deterministic(fa));
+                        }
+                    } else if statement == Statement::RegexDef {
+                        if let (Token::Operand(name), Token::Operand(pattern)) =
+                            (&tokens[1], &tokens[3])
+                        {
+                            // with whitespace, symbols are words like `login`
+                            let fa = if pattern.contains(char::is_whitespace) {
+                                FiniteAutomata::from_regex_with(pattern, |token| {
+                                    engine.symbols.symbol(token)
+                                })
+                            } else {
+                                FiniteAutomata::from_regex(pattern)
+                            };
+                            let fa = fa.expect("Invalid Regular Expression");
+                            engine
+                                .blocks
+                                .insert(name.to_owned(), Block::Deterministic(fa));
                         }
                     } else if statement == Statement::Out {
                         if let Token::Operand(name) = &tokens[1] {
                             if engine.blocks.contains_key(name) {
-                                if let Token::Operand(arg) = &tokens[2] {
-                                    let result = engine.block(name).exec(arg.chars());
-                                    if result.is_none() {
-                                        panic!("Invalid Output Syntax");
-                                    }
-                                    engine.output.push(format!("{}", result.unwrap()));
+                                let is_traced =
+                                    tokens.get(3) == Some(&Token::Operator("trace".to_string()));
+                                let is_explained = tokens[0] == Token::Operator(">?".to_string());
+                                if let (Token::Operand(arg), true) = (&tokens[2], is_explained) {
+                                    let (input, symbols) = engine.input(name, arg);
+                                    let explanation = engine.explain(name, &input, &symbols);
+                                    engine.output.extend(explanation);
+                                } else if let (Token::Operand(arg), true) = (&tokens[2], is_traced)
+                                {
+                                    let (input, _) = engine.input(name, arg);
+                                    let trace = engine.trace(name, &input);
+                                    engine.output.extend(trace);
+                                } else if let Token::Operand(arg) = &tokens[2] {
+                                    let (input, _) = engine.input(name, arg);
+                                    let result = engine.run(name, &input);
+                                    engine.output.push(result);
+                                } else if let Token::Operator(kind) = &tokens[2] {
+                                    let description = engine.describe(name, kind, &tokens[3..]);
+                                    engine.output.push(description);
                                 }
                             }
                         } else {
@@ -59,79 +238,188 @@ impl Engine {
                 Scope::BlockScope if curr_block.is_some() => {
                     if statement == Statement::AlphabetDef {
                         let mut alphas = Vec::new();
+                        let mut seen = HashSet::new();
                         for token in &tokens[1..] {
                             if let Token::Operator(_) = token {
                                 break;
                             }
                             if let Token::Operand(c) = token {
-                                alphas.push(c.chars().nth(0).unwrap());
+                                for a in engine.symbols_of(c) {
+                                    if seen.insert(a) {
+                                        alphas.push(a);
+                                    }
+                                }
                             }
                         }
                         if let Some(name) = &curr_block {
-                            let block = engine.blocks.get_mut(name).unwrap();
-                            block.add_alphabets(&alphas);
+                            match engine.blocks.get_mut(name).unwrap() {
+                                Block::Deterministic(fa) => fa.add_alphabets(&alphas),
+                                Block::Nondeterministic(nfa) => nfa.add_alphabets(&alphas),
+                                Block::Mealy(mealy) => mealy.add_alphabets(&alphas),
+                                Block::Moore(moore) => moore.add_alphabets(&alphas),
+                                Block::Pushdown(pda) => pda.add_alphabets(&alphas),
+                                Block::Turing(tm) => {
+                                    if alphas.iter().any(|a| SymbolTable::is_reserved(*a)) {
+                                        panic!("{}", SINGLE_SYMBOLS);
+                                    }
+                                    tm.add_alphabets(&alphas)
+                                }
+                                Block::Weighted(weighted) => {
+                                    each_weighted!(weighted, wfa => wfa.add_alphabets(&alphas))
+                                }
+                            }
+                        }
+                    } else if statement == Statement::StackAlphabetDef {
+                        // `(Z)` marks the initial stack symbol or the blank
+                        let mut marked = None;
+                        let alphas: Vec<char> = tokens[1..]
+                            .iter()
+                            .map_while(|token| match token {
+                                Token::Operand(c) if is_initial_state(c) => {
+                                    let a = single_symbol(&state_from_initial_state(c.clone()));
+                                    marked = Some(a);
+                                    Some(a)
+                                }
+                                Token::Operand(c) => Some(single_symbol(c)),
+                                Token::Operator(_) => None,
+                            })
+                            .collect();
+                        if let Some(name) = &curr_block {
+                            match engine.blocks.get_mut(name).unwrap() {
+                                Block::Pushdown(pda) => {
+                                    pda.add_stack_alphabets(&alphas);
+                                    pda.initial_stack(marked.as_slice());
+                                }
+                                Block::Turing(tm) => {
+                                    tm.add_tape_alphabets(&alphas);
+                                    if let Some(blank) = marked {
+                                        if tm.alphabets().contains(&blank) {
+                                            panic!(
+                                                "The blank of a TM block cannot be an input symbol"
+                                            );
+                                        }
+                                        tm.blank(blank);
+                                    }
+                                }
+                                _ => panic!("Stack or tape alphabets need a PDA or TM block"),
+                            }
                         }
                     } else if statement == Statement::StateDef {
                         let mut states = Vec::new();
-                        let mut init_state = String::new();
-                        let mut final_state = String::new();
-                        let mut is_init = false;
-                        let mut is_final = false;
+                        let mut init_states = Vec::new();
+                        let mut final_states = Vec::new();
+                        let mut outputs = Vec::new();
                         tokens.remove(0);
                         for token in tokens.into_iter() {
                             if let Token::Operator(_) = token {
                                 break;
                             }
                             if let Token::Operand(mut c) = token {
-                                if is_final_state(&c) {
-                                    if !is_final {
+                                let mut is_init = false;
+                                let mut is_final = false;
+                                loop {
+                                    if is_initial_state(&c) && !is_init {
+                                        c = state_from_initial_state(c);
+                                        is_init = true;
+                                    } else if is_final_state(&c) && !is_final {
                                         c = state_from_final_state(c);
                                         is_final = true;
                                     } else {
-                                        panic!("Cannot have more than one final state");
+                                        break;
                                     }
-                                    if is_initial_state(&c) {
-                                        if !is_init {
-                                            c = state_from_initial_state(c);
-                                            is_init = true;
-                                        } else {
-                                            panic!("Cannot have more than one initial state");
-                                        }
-                                        init_state = c.clone();
-                                    }
-                                    final_state = c.clone();
                                 }
-                                if is_initial_state(&c) {
-                                    if !is_init {
-                                        c = state_from_initial_state(c);
-                                        is_init = true;
-                                    } else {
-                                        panic!("Cannot have more than one final state");
-                                    }
-                                    if is_final_state(&c) {
-                                        if !is_final {
-                                            c = state_from_final_state(c);
-                                            is_final = true;
-                                        } else {
-                                            panic!("Cannot have more than one initial state");
-                                        }
-                                        final_state = c.clone();
-                                    }
-                                    init_state = c.clone();
+                                // Moore states carry their output, like `A:0`
+                                if let Some((state, output)) = c.split_once(':') {
+                                    outputs.push((state.to_owned(), output.to_owned()));
+                                    c = state.to_owned();
+                                }
+                                if is_init {
+                                    init_states.push(c.clone());
+                                }
+                                if is_final {
+                                    final_states.push(c.clone());
                                 }
-
                                 states.push(c);
                             }
                         }
                         if let Some(name) = &curr_block {
+                            if let Some(source) = engine.sources.get_mut(name) {
+                                source.states_line = line;
+                            }
                             let block = engine.blocks.get_mut(name).unwrap();
-                            block.add_states(&states);
-                            if is_init && is_final {
-                                block.initial_state(init_state);
-                                block.final_state(final_state);
-                            } else {
+                            let is_transducer = matches!(block, Block::Mealy(_) | Block::Moore(_));
+                            // a PDA without final states accepts by empty stack
+                            let needs_final =
+                                !is_transducer && !matches!(block, Block::Pushdown(_));
+                            if init_states.is_empty() || (needs_final && final_states.is_empty()) {
                                 panic!("No initial or final state found!");
                             }
+                            if is_transducer && !final_states.is_empty() {
+                                panic!("Mealy and Moore blocks have no final states");
+                            }
+                            let is_moore = matches!(block, Block::Moore(_));
+                            if !is_moore && !outputs.is_empty() {
+                                panic!("State outputs need a MOORE block");
+                            }
+                            if is_moore && outputs.len() != states.len() {
+                                panic!("Every state of a Moore block needs an output, like A:0");
+                            }
+                            match block {
+                                Block::Deterministic(fa) => {
+                                    if init_states.len() > 1 {
+                                        panic!("Cannot have more than one initial state");
+                                    }
+                                    fa.add_states(&states);
+                                    fa.initial_state(init_states.remove(0));
+                                    fa.final_states(&final_states);
+                                }
+                                Block::Nondeterministic(nfa) => {
+                                    nfa.add_states(&states);
+                                    nfa.initial_states(&init_states);
+                                    nfa.final_states(&final_states);
+                                }
+                                Block::Mealy(mealy) => {
+                                    if init_states.len() > 1 {
+                                        panic!("Cannot have more than one initial state");
+                                    }
+                                    mealy.add_states(&states);
+                                    mealy.initial_state(init_states.remove(0));
+                                }
+                                Block::Moore(moore) => {
+                                    if init_states.len() > 1 {
+                                        panic!("Cannot have more than one initial state");
+                                    }
+                                    moore.add_states(&states);
+                                    moore.initial_state(init_states.remove(0));
+                                    for (state, output) in outputs {
+                                        moore.state_output(state, output);
+                                    }
+                                }
+                                Block::Pushdown(pda) => {
+                                    if init_states.len() > 1 {
+                                        panic!("Cannot have more than one initial state");
+                                    }
+                                    pda.add_states(&states);
+                                    pda.initial_state(init_states.remove(0));
+                                    if final_states.is_empty() {
+                                        pda.accept_by(Acceptance::EmptyStack);
+                                    }
+                                    pda.final_states(&final_states);
+                                }
+                                Block::Turing(tm) => {
+                                    if init_states.len() > 1 {
+                                        panic!("Cannot have more than one initial state");
+                                    }
+                                    tm.add_states(&states);
+                                    tm.initial_state(init_states.remove(0));
+                                    tm.final_states(&final_states);
+                                }
+                                Block::Weighted(weighted) => each_weighted!(weighted, wfa => {
+                                    wfa.add_states(&states);
+                                    wfa.initial_states(&init_states);
+                                    wfa.final_states(&final_states);
+                                }),
+                            }
                         }
                     } else if statement == Statement::DeltaDef {
                         scope = Scope::DeltaScope;
@@ -146,8 +434,10 @@ impl Engine {
                     if statement == Statement::Transition {
                         let mut i = 1;
                         let mut state1 = String::new();
-                        let mut state2 = String::new();
+                        let mut states2 = Vec::new();
                         let mut alphas = Vec::new();
+                        let mut is_epsilon = false;
+                        let mut weight = None;
                         for token in tokens.into_iter() {
                             match token {
                                 Token::Operand(c) if i == 1 => {
@@ -155,28 +445,160 @@ impl Engine {
                                     state1 = c;
                                 }
                                 Token::Operator(_) if i == 2 => i += 1,
+                                Token::Operand(c) if i == 3 && c == EPSILON => is_epsilon = true,
                                 Token::Operand(c) if i == 3 => {
-                                    alphas.push(c.chars().nth(0).unwrap())
+                                    let name = curr_block.as_ref().unwrap();
+                                    alphas.extend(engine.transition_symbols(name, &c))
                                 }
                                 Token::Operator(_) if i == 3 => i += 1,
-                                Token::Operand(c) if i == 4 => {
-                                    state2 = c.to_owned();
-                                    i += 1;
-                                }
-                                _ if i > 4 => {
-                                    break;
-                                }
+                                Token::Operand(c) if i == 4 => states2.push(c),
+                                Token::Operator(op) if i == 4 && op == ":" => i += 1,
+                                Token::Operand(w) if i == 5 => weight = Some(w),
+                                Token::Operator(_) if i >= 4 => break,
                                 _ => panic!("Not in Delta"),
                             }
                         }
                         if let Some(name) = &curr_block {
+                            if let Some(source) = engine.sources.get_mut(name) {
+                                source.transitions.push(Declared {
+                                    line,
+                                    state: state1.clone(),
+                                    symbols: alphas.clone(),
+                                    next: states2.clone(),
+                                });
+                            }
                             let block = engine.blocks.get_mut(name).unwrap();
-                            block.add_action(state1, &alphas, state2);
+                            if weight.is_some() && !matches!(block, Block::Weighted(_)) {
+                                panic!("{}", WEIGHTS);
+                            }
+                            match block {
+                                Block::Deterministic(_) | Block::Moore(_) | Block::Weighted(_)
+                                    if is_epsilon =>
+                                {
+                                    panic!("Epsilon transitions need an NFA block");
+                                }
+                                Block::Deterministic(fa) => {
+                                    if states2.len() != 1 {
+                                        panic!("Deterministic block needs exactly one next state");
+                                    }
+                                    fa.add_action(state1, &alphas, states2.remove(0));
+                                }
+                                Block::Nondeterministic(nfa) => {
+                                    if is_epsilon {
+                                        nfa.add_epsilon_action(state1.clone(), &states2);
+                                    }
+                                    nfa.add_action(state1, &alphas, &states2)
+                                }
+                                Block::Mealy(_) => panic!("Mealy transitions need an output"),
+                                Block::Moore(moore) => {
+                                    if states2.len() != 1 {
+                                        panic!("Deterministic block needs exactly one next state");
+                                    }
+                                    moore.add_action(state1, &alphas, states2.remove(0));
+                                }
+                                Block::Pushdown(_) => panic!("{}", PDA_TRANSITION),
+                                Block::Turing(_) => panic!("{}", TM_TRANSITION),
+                                Block::Weighted(weighted) => {
+                                    each_weighted!(weighted, wfa => {
+                                        add_weighted(wfa, &state1, &alphas, &states2, weight)
+                                    })
+                                }
+                            }
+                        }
+                    } else if statement == Statement::PDATransition {
+                        // `A = a, X / YX -> B`, the operands are at fixed places
+                        let operand = |i: usize| match &tokens[i] {
+                            Token::Operand(c) if c == EPSILON => None,
+                            Token::Operand(c) => Some(c.clone()),
+                            Token::Operator(_) => panic!("Not in Delta"),
+                        };
+                        let state1 = operand(0).unwrap();
+                        let alpha = operand(2).map(|a| engine.symbols.symbol(&a));
+                        let top = operand(4).map(|x| single_symbol(&x));
+                        let push: Vec<char> = operand(6).unwrap_or_default().chars().collect();
+                        let state2 = operand(8).unwrap();
+                        if let Some(name) = &curr_block {
+                            match engine.blocks.get_mut(name).unwrap() {
+                                Block::Pushdown(pda) => {
+                                    pda.add_action(state1, alpha, top, &push, state2)
+                                }
+                                _ => panic!("Stack transitions need a PDA block"),
+                            }
+                        }
+                    } else if statement == Statement::TMTransition {
+                        // `A = a b -> B, x, R`
+                        let arrow = tokens
+                            .iter()
+                            .position(|t| *t == Token::Operator("->".to_string()))
+                            .unwrap();
+                        let operand = |i: usize| match &tokens[i] {
+                            Token::Operand(c) => c.clone(),
+                            Token::Operator(_) => panic!("Not in Delta"),
+                        };
+                        let state1 = operand(0);
+                        let read: Vec<char> =
+                            (2..arrow).map(|i| single_symbol(&operand(i))).collect();
+                        let state2 = operand(arrow + 1);
+                        let write = single_symbol(&operand(arrow + 3));
+                        let direction = match operand(arrow + 5).as_str() {
+                            "L" => Move::Left,
+                            "R" => Move::Right,
+                            _ => Move::Stay,
+                        };
+                        if let Some(name) = &curr_block {
+                            match engine.blocks.get_mut(name).unwrap() {
+                                Block::Turing(tm) => {
+                                    tm.add_action(state1, &read, state2, write, direction)
+                                }
+                                _ => panic!("Head moves need a TM block"),
+                            }
+                        }
+                    } else if statement == Statement::MealyTransition
+                        || statement == Statement::MealySelfLoop
+                    {
+                        // `A = 0 / x -> B` or `@A = 0 / x`, skipping the `@`
+                        let mut items = tokens
+                            .into_iter()
+                            .filter(|t| *t != Token::Operator("@".to_string()));
+                        let state1 = match items.next() {
+                            Some(Token::Operand(state)) => state,
+                            _ => panic!("Not in Delta"),
+                        };
+                        items.next();
+                        let mut alphas = Vec::new();
+                        let mut output = String::new();
+                        let mut states2 = Vec::new();
+                        let mut i = 3;
+                        for token in items {
+                            match token {
+                                Token::Operand(c) if i == 3 => {
+                                    alphas.push(engine.symbols.symbol(&c))
+                                }
+                                Token::Operand(c) if i == 4 && c != EPSILON => output = c,
+                                Token::Operand(c) if i == 5 => states2.push(c),
+                                Token::Operator(op) if op == "/" || op == "->" => i += 1,
+                                Token::Operand(_) | Token::Operator(_) => break,
+                            }
+                        }
+                        if statement == Statement::MealySelfLoop {
+                            states2.push(state1.clone());
+                        }
+                        if let Some(name) = &curr_block {
+                            match engine.blocks.get_mut(name).unwrap() {
+                                Block::Mealy(mealy) => {
+                                    if states2.len() != 1 {
+                                        panic!("Mealy block needs exactly one next state");
+                                    }
+                                    mealy.add_action(state1, &alphas, output, states2.remove(0));
+                                }
+                                _ => panic!("Transition outputs need a MEALY block"),
+                            }
                         }
                     } else if statement == Statement::SelfLoop {
                         let mut i = 1;
                         let mut state = String::new();
                         let mut alphas = Vec::new();
+                        let mut weight = None;
                         tokens.remove(0);
                         for token in tokens.into_iter() {
                             match token {
@@ -186,16 +608,48 @@ impl Engine {
                                 }
                                 Token::Operator(_) if i == 2 => i += 1,
                                 Token::Operand(c) if i == 3 => {
-                                    alphas.push(c.chars().nth(0).unwrap());
+                                    let name = curr_block.as_ref().unwrap();
+                                    alphas.extend(engine.transition_symbols(name, &c))
                                 }
+                                Token::Operator(op) if i == 3 && op == ":" => i += 1,
+                                Token::Operand(w) if i == 4 => weight = Some(w),
                                 Token::Operator(_) if i >= 2 => {
                                     break;
                                 }
                                 _ => panic!("Not in Delta"),
                             }
-                            if let Some(name) = &curr_block {
-                                let block = engine.blocks.get_mut(name).unwrap();
-                                block.add_action(state.clone(), &alphas, state.clone());
+                        }
+                        if let Some(name) = &curr_block {
+                            if let Some(source) = engine.sources.get_mut(name) {
+                                source.transitions.push(Declared {
+                                    line,
+                                    state: state.clone(),
+                                    symbols: alphas.clone(),
+                                    next: vec![state.clone()],
+                                });
+                            }
+                            let block = engine.blocks.get_mut(name).unwrap();
+                            if weight.is_some() && !matches!(block, Block::Weighted(_)) {
+                                panic!("{}", WEIGHTS);
+                            }
+                            match block {
+                                Block::Deterministic(fa) => {
+                                    fa.add_action(state.clone(), &alphas, state.clone())
+                                }
+                                Block::Nondeterministic(nfa) => {
+                                    nfa.add_action(state.clone(), &alphas, &[state.clone()])
+                                }
+                                Block::Mealy(_) => {
+                                    panic!("Mealy transitions need an output")
+                                }
+                                Block::Moore(moore) => {
+                                    moore.add_action(state.clone(), &alphas, state.clone())
+                                }
+                                Block::Pushdown(_) => panic!("{}", PDA_TRANSITION),
+                                Block::Turing(_) => panic!("{}", TM_TRANSITION),
+                                Block::Weighted(weighted) => each_weighted!(weighted, wfa => {
+                                    add_weighted(wfa, &state, &alphas, &[state.clone()], weight)
+                                }),
                             }
                         }
                     } else if statement == Statement::DeltaDefEnd {
@@ -209,13 +663,346 @@ impl Engine {
         }
         engine
     }
-    pub fn block(&self, name: &str) -> &FiniteAutomata {
+    pub fn block(&self, name: &str) -> &Block {
         self.blocks.get(name).unwrap()
     }
 
     pub fn output(&self) -> &[String] {
         &self.output
     }
+
+    /// Symbols an operand of an alphabet stands for: a symbol, a range or a
+    /// class.
+    fn symbols_of(&mut self, item: &str) -> Vec<char> {
+        match symbol_class(item) {
+            Some(symbols) if symbols.iter().any(|a| SymbolTable::is_reserved(*a)) => {
+                panic!("{} has chars reserved for multi-character symbols", item)
+            }
+            Some(symbols) => symbols,
+            None => vec![self.symbols.symbol(item)],
+        }
+    }
+
+    /// Chars standing for `arg` as an input of block `name`, read with a copy
+    /// of the symbol table so unknown tokens do not become symbols.
+    fn input(&self, name: &str, arg: &str) -> (String, SymbolTable) {
+        let mut symbols = self.symbols.clone();
+        let input = symbols.input(arg, self.block(name).alphabets());
+        (input, symbols)
+    }
+
+    /// Same as [`Engine::symbols_of`] for a transition of block `name`, where
+    /// `!` stands for every other symbol of its alphabet.
+    fn transition_symbols(&mut self, name: &str, item: &str) -> Vec<char> {
+        match item.strip_prefix('!') {
+            Some(item) => {
+                let excluded: HashSet<char> = self.symbols_of(item).into_iter().collect();
+                self.block(name)
+                    .alphabets()
+                    .iter()
+                    .copied()
+                    .filter(|a| !excluded.contains(a))
+                    .collect()
+            }
+            None => self.symbols_of(item),
+        }
+    }
+
+    /// Issues of the FA and NFA blocks, sorted by line: transitions with
+    /// undeclared states or symbols, declared twice or conflicting, then the
+    /// ones found by [`FiniteAutomata::validate`] and
+    /// [`NondeterministicAutomata::validate`]. Unreachable and dead states
+    /// point at the states declaration, and missing transitions at the last
+    /// transition of their state, if any.
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for (name, source) in &self.sources {
+            let mut issues = Vec::new();
+            let (states, alphabets, is_deterministic, found) = match self.block(name) {
+                Block::Deterministic(fa) => (fa.states(), fa.alphabets(), true, fa.validate()),
+                Block::Nondeterministic(nfa) => {
+                    (nfa.states(), nfa.alphabets(), false, nfa.validate())
+                }
+                _ => continue,
+            };
+            let mut declared: HashMap<(&str, char), Vec<&str>> = HashMap::new();
+            for transition in &source.transitions {
+                let line = transition.line;
+                let names = [&transition.state].into_iter().chain(&transition.next);
+                let undeclared: Vec<&String> = names.filter(|s| !states.contains(s)).collect();
+                for state in &undeclared {
+                    issues.push((line, Issue::UndeclaredState(state.to_string())));
+                }
+                for a in &transition.symbols {
+                    if !alphabets.contains(a) {
+                        issues.push((line, Issue::UnknownSymbol(self.symbols.token(*a))));
+                        continue;
+                    }
+                    if !undeclared.is_empty() {
+                        continue;
+                    }
+                    let targets = declared.entry((&transition.state, *a)).or_default();
+                    for next in &transition.next {
+                        let state = transition.state.clone();
+                        let symbol = self.symbols.token(*a);
+                        if targets.contains(&next.as_str()) {
+                            issues.push((line, Issue::Duplicate { state, symbol }));
+                        } else if is_deterministic && !targets.is_empty() {
+                            let next = next.clone();
+                            issues.push((
+                                line,
+                                Issue::Conflict {
+                                    state,
+                                    symbol,
+                                    next,
+                                },
+                            ));
+                        }
+                        targets.push(next);
+                    }
+                }
+            }
+            for issue in found {
+                let (line, issue) = match issue {
+                    Issue::Missing { state, symbol } => {
+                        let line = source
+                            .transitions
+                            .iter()
+                            .rev()
+                            .find(|t| t.state == state)
+                            .map_or(source.states_line, |t| t.line);
+                        let symbol = self.symbols.text(&symbol);
+                        (line, Issue::Missing { state, symbol })
+                    }
+                    issue => (source.states_line, issue),
+                };
+                issues.push((line, issue));
+            }
+            diagnostics.extend(issues.into_iter().map(|(line, issue)| Diagnostic {
+                line,
+                block: name.clone(),
+                message: issue.to_string(),
+                issue,
+            }));
+        }
+        diagnostics.sort_by_key(|d| d.line);
+        diagnostics
+    }
+
+    /// Checks that two named blocks accept the same language.
+    pub fn equivalent(&self, left: &str, right: &str) -> Result<(), Counterexample> {
+        equivalent(&self.finite_automata(left), &self.finite_automata(right)).map_err(|c| {
+            Counterexample {
+                input: self.symbols.word(&c.input),
+                ..c
+            }
+        })
+    }
+
+    /// Checks that every input accepted by the `left` block is accepted by the
+    /// `right` one, the error is an input only `left` accepts.
+    pub fn included(&self, left: &str, right: &str) -> Result<(), String> {
+        self.finite_automata(left)
+            .included_in(&self.finite_automata(right))
+            .map_err(|input| self.symbols.word(&input))
+    }
+
+    /// Evaluates the right hand side of a derived block. Prefix operations bind
+    /// to the block right after them and binary ones are applied left to right,
+    /// so `minimize A & B` is `(minimize A) & B`.
+    fn derive(&self, tokens: &[Token]) -> FiniteAutomata {
+        let mut tokens = tokens
+            .iter()
+            .take_while(|t| **t != Token::Operator("//".to_string()));
+        let mut fa = self.derive_operand(&mut tokens);
+        while let Some(token) = tokens.next() {
+            let other = self.derive_operand(&mut tokens);
+            fa = match token {
+                Token::Operator(op) if op == "&" => fa.intersection(&other),
+                Token::Operator(op) if op == "|" => fa.union(&other),
+                Token::Operator(op) if op == "-" => fa.difference(&other),
+                Token::Operator(op) if op == "^" => fa.symmetric_difference(&other),
+                Token::Operator(op) if op == "." => fa.concat(&other),
+                _ => panic!("Invalid Derived Block Syntax"),
+            };
+        }
+        fa
+    }
+
+    fn derive_operand<'a>(&self, tokens: &mut impl Iterator<Item = &'a Token>) -> FiniteAutomata {
+        match tokens.next() {
+            Some(Token::Operand(name)) => self.finite_automata(name),
+            Some(Token::Operator(op)) => {
+                let fa = self.derive_operand(tokens);
+                match op.as_str() {
+                    "minimize" => fa.minimize(),
+                    "trim" => fa.trim(),
+                    "complete" => fa.complete(),
+                    "complement" => fa.complement(),
+                    "reverse" => fa.reverse(),
+                    "star" => fa.star(),
+                    "plus" => fa.plus(),
+                    _ => panic!("Unknown Operation {}", op),
+                }
+            }
+            None => panic!("Invalid Derived Block Syntax"),
+        }
+    }
+
+    /// Output of an Out statement running a block on `input`: whether it is
+    /// accepted, or what a Mealy or Moore block writes.
+    fn run(&self, name: &str, input: &str) -> String {
+        let result = match self.block(name) {
+            Block::Deterministic(fa) => fa.exec(input.chars()).map(|r| r.to_string()),
+            Block::Nondeterministic(nfa) => Some(nfa.exec(input.chars()).to_string()),
+            Block::Mealy(mealy) => mealy.exec(input.chars()).map(written),
+            Block::Moore(moore) => moore.exec(input.chars()).map(written),
+            Block::Pushdown(pda) => Some(
+                pda.exec(input.chars())
+                    .map_or(UNDECIDED.to_string(), |result| result.to_string()),
+            ),
+            Block::Turing(tm) => {
+                let run = tm.exec(input.chars());
+                Some(format!("{} {}", run.halt, written(run.tape)))
+            }
+            Block::Weighted(weighted) => {
+                Some(each_weighted!(weighted, wfa => wfa.exec(input.chars()).to_string()))
+            }
+        };
+        match result {
+            Some(result) => result,
+            None if self.options.reject_undefined => false.to_string(),
+            None => panic!("Invalid Output Syntax"),
+        }
+    }
+
+    /// Configurations a TM block goes through on `input`, one per step, then
+    /// the outcome as printed by [`Engine::run`].
+    fn trace(&self, name: &str, input: &str) -> Vec<String> {
+        let tm = match self.block(name) {
+            Block::Turing(tm) => tm,
+            _ => panic!("Only TM blocks can be traced"),
+        };
+        let (run, trace) = tm.exec_trace(input.chars());
+        let mut lines: Vec<String> = trace.iter().map(|config| config.to_string()).collect();
+        lines.push(format!("{} {}", run.halt, written(run.tape)));
+        lines
+    }
+
+    /// Transitions a block takes on `input`, one per line, then why it is
+    /// accepted or rejected.
+    fn explain(&self, name: &str, input: &str, symbols: &SymbolTable) -> Vec<String> {
+        let (outcome, steps) = self.finite_automata(name).exec_trace(input.chars());
+        let mut lines: Vec<String> = steps
+            .iter()
+            .map(|step| symbols.text(&step.to_string()))
+            .collect();
+        lines.push(symbols.text(&outcome.to_string()));
+        lines
+    }
+
+    /// Output of an Out statement about the language of a block rather than
+    /// about one input, like `> LastOne accepted 10` or `> LastOne regex`.
+    fn describe(&self, name: &str, kind: &str, args: &[Token]) -> String {
+        let fa = self.finite_automata(name);
+        let args: Vec<&Token> = args
+            .iter()
+            .take_while(|t| **t != Token::Operator("//".to_string()))
+            .collect();
+        match (kind, args.as_slice()) {
+            ("regex", []) => {
+                let words = fa.alphabets().iter().any(|a| SymbolTable::is_reserved(*a));
+                match fa.to_regex_with(|a| self.symbols.token(a)) {
+                    None => "∅".to_string(),
+                    // spaced so that an RE block reads it as words
+                    Some(regex) if words && !regex.contains(char::is_whitespace) => {
+                        format!("/ {} /", regex)
+                    }
+                    Some(regex) => format!("/{}/", regex),
+                }
+            }
+            ("accepted" | "rejected", [Token::Operand(count)]) => {
+                let words = if kind == "accepted" {
+                    fa.accepted_words(usize::MAX)
+                } else {
+                    fa.rejected_words(usize::MAX)
+                };
+                let words: Vec<String> = words
+                    .take(count.parse().expect("Invalid Count"))
+                    .map(|w| {
+                        if w.is_empty() {
+                            EPSILON.to_string()
+                        } else {
+                            self.symbols.word(&w)
+                        }
+                    })
+                    .collect();
+                format!("{{{}}}", words.join(", "))
+            }
+            _ => panic!("Invalid Output Syntax"),
+        }
+    }
+
+    /// Deterministic form of a block, NFA blocks get determinized.
+    fn finite_automata(&self, name: &str) -> FiniteAutomata {
+        match self.blocks.get(name) {
+            Some(Block::Deterministic(fa)) => fa.clone(),
+            Some(Block::Nondeterministic(nfa)) => nfa.determinize(),
+            Some(
+                Block::Mealy(_)
+                | Block::Moore(_)
+                | Block::Pushdown(_)
+                | Block::Turing(_)
+                | Block::Weighted(_),
+            ) => {
+                panic!("Block {} is not a finite automata", name)
+            }
+            None => panic!("Unknown Block {}", name),
+        }
+    }
+}
+
+const PDA_TRANSITION: &str = "PDA transitions need a stack, like A = a, X / YX -> B";
+const TM_TRANSITION: &str = "TM transitions need a write and a move, like A = a -> B, b, R";
+const WEIGHTS: &str = "Weights need a WFA block";
+const SINGLE_SYMBOLS: &str = "PDA stack and TM symbols are single characters";
+/// Printed for a PDA block that reaches the step limit before deciding.
+const UNDECIDED: &str = "undecided";
+
+/// Adds the moves of a transition of a WFA block, a missing weight being the
+/// `one` of its semiring.
+fn add_weighted<S: Semiring>(
+    wfa: &mut WeightedAutomata<S>,
+    state1: &str,
+    alphas: &[char],
+    states2: &[String],
+    weight: Option<String>,
+) {
+    let weight = match weight {
+        Some(w) => w.parse().unwrap_or_else(|_| panic!("Invalid Weight {}", w)),
+        None => S::one(),
+    };
+    for state2 in states2 {
+        wfa.add_action(state1.to_owned(), alphas, state2.clone(), weight.clone());
+    }
+}
+
+/// Output of a transducer as printed by an Out statement, `ε` when empty.
+fn written(output: String) -> String {
+    if output.is_empty() {
+        EPSILON.to_string()
+    } else {
+        output
+    }
+}
+
+/// Char of a stack or tape symbol of a PDA or TM block.
+fn single_symbol(item: &str) -> char {
+    let mut chars = item.chars();
+    match (chars.next(), chars.next()) {
+        (Some(a), None) => a,
+        _ => panic!("{}, found {}", SINGLE_SYMBOLS, item),
+    }
 }
 
 fn is_initial_state(c: &str) -> bool {
@@ -237,3 +1024,559 @@ fn state_from_final_state(mut c: String) -> String {
     c.remove(c.len() - 1);
     c
 }
+
+#[cfg(test)]
+mod engine_tests {
+    use super::{Engine, EngineOptions, Issue};
+
+    const CODE: &str = "
+FA ZeroOne {
+    := 0 1
+    + (A) B {C}
+    => [
+        A = 0 -> B
+        B = 1 -> C
+    ]
+}
+> ZeroOne \"01\"
+> ZeroOne \"11\"";
+
+    /// Engine running `code`, undefined transitions rejecting.
+    fn strict(code: impl Into<String>) -> Engine {
+        let options = EngineOptions {
+            reject_undefined: true,
+            ..Default::default()
+        };
+        Engine::from_str_with_options(code.into(), options)
+    }
+
+    fn run_strict(code: impl Into<String>) -> Vec<String> {
+        strict(code).output().to_vec()
+    }
+
+    #[test]
+    fn reject_undefined() {
+        assert_eq!(vec!["true", "false"], run_strict(CODE));
+    }
+
+    #[test]
+    fn equivalent() {
+        let code = format!("{}\nFA MinZeroOne = minimize ZeroOne", CODE);
+        let options = EngineOptions {
+            reject_undefined: true,
+            ..Default::default()
+        };
+        let engine = Engine::from_str_with_options(code, options);
+        assert_eq!(Ok(()), engine.equivalent("ZeroOne", "MinZeroOne"));
+    }
+
+    #[test]
+    fn minimize() {
+        // the trap state of the minimal block rejects what ZeroOne cannot read
+        let code = format!(
+            "{}\nFA Min = minimize ZeroOne\n> Min \"11\"\n> Min \"010\"",
+            CODE.replace("> ZeroOne \"11\"", "")
+        );
+        let engine = Engine::from_str(code);
+        assert_eq!(["true", "false", "false"], engine.output());
+    }
+
+    #[test]
+    fn trim() {
+        let code = format!("{}\nFA Trimmed = trim complete ZeroOne", CODE);
+        let options = EngineOptions {
+            reject_undefined: true,
+            ..Default::default()
+        };
+        let engine = Engine::from_str_with_options(code, options);
+        assert_eq!(["A", "B", "C"], engine.finite_automata("Trimmed").states());
+        assert_eq!(Ok(()), engine.equivalent("ZeroOne", "Trimmed"));
+    }
+
+    #[test]
+    fn included() {
+        let code = format!("{}\nFA NotZeroOne = complement ZeroOne", CODE);
+        let options = EngineOptions {
+            reject_undefined: true,
+            ..Default::default()
+        };
+        let engine = Engine::from_str_with_options(code, options);
+        assert_eq!(Ok(()), engine.included("ZeroOne", "ZeroOne"));
+        assert_eq!(
+            Err("".to_string()),
+            engine.included("NotZeroOne", "ZeroOne")
+        );
+    }
+
+    #[test]
+    fn words() {
+        let code = format!("{}\n> ZeroOne accepted 10\n> ZeroOne rejected 3", CODE);
+        let options = EngineOptions {
+            reject_undefined: true,
+            ..Default::default()
+        };
+        let engine = Engine::from_str_with_options(code, options);
+        assert_eq!(["true", "false", "{01}", "{ε, 0, 1}"], engine.output());
+    }
+
+    #[test]
+    fn closure() {
+        let code = format!(
+            "{}\nFA Rev = reverse ZeroOne\nFA Two = ZeroOne . plus Rev\n> Rev accepted 5\n> Two accepted 3",
+            CODE
+        );
+        let options = EngineOptions {
+            reject_undefined: true,
+            ..Default::default()
+        };
+        let engine = Engine::from_str_with_options(code, options);
+        assert_eq!(
+            ["true", "false", "{10}", "{0110, 011010, 01101010}"],
+            engine.output()
+        );
+    }
+
+    #[test]
+    fn regex() {
+        let code = format!("{}\n> ZeroOne regex\nRE Copy = /01/\n> Copy regex", CODE);
+        let options = EngineOptions {
+            reject_undefined: true,
+            ..Default::default()
+        };
+        let engine = Engine::from_str_with_options(code, options);
+        assert_eq!(["true", "false", "/01/", "/01/"], engine.output());
+    }
+
+    #[test]
+    fn regex_rejects() {
+        let code = "RE R = /0*1/\n> R \"001\"\n> R \"10\"\n> R \"2\"";
+        let engine = Engine::from_str(code.to_owned());
+        assert_eq!(["true", "false", "false"], engine.output());
+
+        // blocks derived from a regular expression keep rejecting the others
+        let code = "
+RE R = /0*1/
+RE S = /1*/
+FA X = minimize R
+FA Y = R & S
+FA Z = R | S
+> X \"2\"
+> Y \"2\"
+> Z \"2\"";
+        let engine = Engine::from_str(code.to_owned());
+        assert_eq!(["false", "false", "false"], engine.output());
+    }
+
+    #[test]
+    fn keyword_states() {
+        let code = "
+FA X {
+    := 0 1
+    + (RE) {TM}
+    => [
+        RE = 1 -> TM
+        @RE = 0
+        @TM = 0 1
+    ]
+}
+> X \"10\"
+> X \"0\"";
+        let engine = Engine::from_str(code.to_owned());
+        assert_eq!(["true", "false"], engine.output());
+    }
+
+    #[test]
+    fn mealy() {
+        let code = "
+// writes 1 when the symbol changes
+MEALY Changes {
+    := 0 1
+    + (Z) O
+    => [
+        @Z = 0 / 0
+        Z = 1 / 1 -> O
+        O = 0 / 1 -> Z
+        @O = 1 / 0 // same symbol
+    ]
+}
+> Changes \"0100\"
+> Changes \"\"";
+        let engine = Engine::from_str(code.to_owned());
+        assert_eq!(["0110", "ε"], engine.output());
+    }
+
+    #[test]
+    fn moore() {
+        let code = "
+MOORE TrafficLight {
+    := t r
+    + (R:r) G:g Y:y
+    => [
+        R = t -> G
+        G = t -> Y
+        Y = t -> R
+        @R = r
+        G = r -> R
+        Y = r -> R
+    ]
+}
+> TrafficLight \"tttt\"
+> TrafficLight \"trt\"";
+        let engine = Engine::from_str(code.to_owned());
+        assert_eq!(["rgyrg", "rgrg"], engine.output());
+    }
+
+    const PDA: &str = "
+// as many a as b, accepted by empty stack
+PDA Same {
+    := a b
+    $= (Z) X Y
+    + (A)
+    => [
+        A = a, Z / XZ -> A
+        A = a, X / XX -> A
+        A = a, Y / eps -> A
+        A = b, Z / YZ -> A
+        A = b, Y / YY -> A
+        A = b, X / eps -> A
+        A = eps, Z / eps -> A
+    ]
+}
+> Same \"abba\"
+> Same \"aab\"";
+
+    #[test]
+    fn pda() {
+        let engine = Engine::from_str(PDA.to_owned());
+        assert_eq!(["true", "false"], engine.output());
+    }
+
+    #[test]
+    fn step_limit() {
+        let options = EngineOptions {
+            step_limit: Some(3),
+            ..Default::default()
+        };
+        let engine = Engine::from_str_with_options(PDA.to_owned(), options);
+        assert_eq!(["undecided", "undecided"], engine.output());
+    }
+
+    #[test]
+    #[should_panic(expected = "The blank of a TM block cannot be an input symbol")]
+    fn blank_input() {
+        Engine::from_str("TM Blank {\n:= 0 1\n$= (0)\n+ (A) {B}\n=> [\n]\n}".to_owned());
+    }
+
+    #[test]
+    fn tm() {
+        let code = "
+// adds one to a binary number
+TM Increment {
+    := 0 1
+    $= _
+    + (R) C {H}
+    => [
+        R = 0 -> R, 0, R
+        R = 1 -> R, 1, R
+        R = _ -> C, _, L
+        C = 1 -> C, 0, L
+        C = 0 _ -> H, 1, S
+    ]
+}
+> Increment \"1011\"
+> Increment \"2\"
+> Increment \"1\" trace";
+        let engine = Engine::from_str(code.to_owned());
+        assert_eq!(
+            [
+                "accept 1100",
+                "reject 2",
+                "R [1]",
+                "R 1[_]",
+                "C [1]_",
+                "C [_]0_",
+                "H [1]0_",
+                "accept 10"
+            ],
+            engine.output()
+        );
+    }
+
+    #[test]
+    fn explain() {
+        let code = format!(
+            "{}\n>? ZeroOne \"01\"\n>? ZeroOne \"0\"\n>? ZeroOne \"00\"\n>? ZeroOne \"2\"",
+            CODE.replace("> ZeroOne \"11\"", "")
+        );
+        let engine = Engine::from_str(code);
+        assert_eq!(
+            [
+                "true",
+                "A = 0 -> B",
+                "B = 1 -> C",
+                "accepted",
+                "A = 0 -> B",
+                "rejected",
+                "A = 0 -> B",
+                "stuck at 1, no transition on 0",
+                "stuck at 0, 2 is not in the alphabet"
+            ],
+            engine.output()
+        );
+    }
+
+    #[test]
+    fn tokens() {
+        let code = "
+// a session reads after logging in and ends logged out
+FA Session {
+    := login read logout
+    + (Out) In {Done}
+    => [
+        Out = login -> In
+        @In = read
+        In = logout -> Done
+        Done = login -> In
+    ]
+}
+> Session \"login read, read logout\"
+> Session \"login,logout,login\"
+> Session accepted 2
+>? Session \"read\"
+>? Session \"login raed\"
+> Session regex
+RE Copy = /(login read* logout)+/
+> Copy \"login read logout\"
+> Copy \"login read\"
+RE Reads = /read+/
+RE Words = / read+ /
+> Reads regex
+> Words regex
+> Words \"read read\"";
+        assert_eq!(
+            vec![
+                "true",
+                "false",
+                "{login logout, login read logout}",
+                "stuck at 0, no transition on read",
+                "Out = login -> In",
+                "stuck at 1, raed is not in the alphabet",
+                "/(login read* logout)+/",
+                "true",
+                "false",
+                "/readd*/",
+                "/ read+ /",
+                "true"
+            ],
+            run_strict(code)
+        );
+    }
+
+    #[test]
+    fn block_symbols() {
+        // a token of one block does not change how the others read
+        let code = "
+FA Tok {
+    := read
+    + (A) {B}
+    => [
+        A = read -> B
+    ]
+}
+FA Letters {
+    := r e a d
+    + (A) B C D {E}
+    => [
+        A = r -> B
+        B = e -> C
+        C = a -> D
+        D = d -> E
+    ]
+}
+> Letters \"read\"
+> Tok \"read\"
+> Tok \"r e a d\"";
+        assert_eq!(vec!["true", "true", "false"], run_strict(code));
+    }
+
+    #[test]
+    #[should_panic(expected = "PDA stack and TM symbols are single characters")]
+    fn single_symbols() {
+        Engine::from_str(
+            "
+TM Long {
+    := ab
+    $= (_)
+    + (A) {B}
+    => [
+        A = _ -> B, _, R
+    ]
+}"
+            .to_string(),
+        );
+    }
+
+    #[test]
+    fn classes() {
+        let code = "
+FA Identifier {
+    := a-z A-Z \\w
+    + (Start) {Rest}
+    => [
+        Start = !\\d -> Rest
+        @Rest = \\w
+    ]
+}
+FA NotZero {
+    := \\d
+    + (A) {B}
+    => [
+        A = !0 -> B
+        @B = \\d
+    ]
+}
+> Identifier \"snake_case1\"
+> Identifier \"1st\"
+> NotZero \"907\"
+> NotZero \"07\"";
+        let options = EngineOptions {
+            reject_undefined: true,
+            ..Default::default()
+        };
+        let engine = Engine::from_str_with_options(code.to_owned(), options);
+        assert_eq!(["true", "false", "true", "false"], engine.output());
+        assert_eq!(63, engine.block("Identifier").alphabets().len());
+    }
+
+    #[test]
+    fn validate() {
+        let code = "
+FA Typos {
+    := 0 1
+    + (A) B {C} D
+    => [
+        A = 0 -> B
+        A = 0 -> C
+        B = 1 -> Cc
+        @B = 2
+        A = 1 -> B
+        A = 1 -> B
+        @C = 0 1
+    ]
+}
+NFA Loose {
+    := 0 1
+    + (A) B {C} D
+    => [
+        A = 0 -> B C
+        B = ε -> C
+        @C = 0 1
+        D = 0 -> C
+        A = 3 -> B
+    ]
+}";
+        let engine = Engine::from_str(code.to_owned());
+        let diagnostics: Vec<(usize, String)> = engine
+            .validate()
+            .into_iter()
+            .map(|d| (d.line, d.message))
+            .collect();
+        let expected = [
+            (4, "D is unreachable"),
+            (4, "B can never reach a final state"),
+            (4, "D can never reach a final state"),
+            (7, "A = 0 is declared again going to C"),
+            (8, "state Cc is not declared"),
+            (9, "2 is not in the alphabet"),
+            (11, "A = 1 is declared twice"),
+            (17, "D is unreachable"),
+            (22, "D has no transition on 1"),
+            (23, "3 is not in the alphabet"),
+            (23, "A has no transition on 1"),
+        ];
+        let expected: Vec<(usize, String)> =
+            expected.iter().map(|(l, m)| (*l, m.to_string())).collect();
+        assert_eq!(expected, diagnostics);
+        assert!(engine.validate()[3].issue.is_error());
+
+        // only missing transitions, C has none at all
+        let engine = strict(CODE);
+        let diagnostics = engine.validate();
+        let lines: Vec<usize> = diagnostics.iter().map(|d| d.line).collect();
+        assert_eq!(vec![4, 4, 6, 7], lines);
+        assert!(diagnostics
+            .iter()
+            .all(|d| matches!(d.issue, Issue::Missing { .. })));
+
+        // multi-character symbols are spelled out
+        let code = "
+FA Session {
+    := login logout
+    + (A) {B}
+    => [
+        A = login -> B
+        A = login -> B
+    ]
+}";
+        let diagnostics = strict(code).validate();
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        let expected = [
+            "B has no transition on login",
+            "B has no transition on logout",
+            "A = login is declared twice",
+            "A has no transition on logout",
+        ];
+        assert_eq!(expected, messages.as_slice());
+        let duplicate = Issue::Duplicate {
+            state: "A".to_string(),
+            symbol: "login".to_string(),
+        };
+        assert_eq!(duplicate, diagnostics[2].issue);
+    }
+
+    #[test]
+    fn wfa() {
+        let code = "
+WFA Coin probability {
+    := h t
+    + (A) {B}
+    => [
+        A = h -> A B : 0.5
+        @A = t : 0.5
+        @B = h t
+    ]
+}
+WFA Paths counting {
+    := a
+    + (A) B {C}
+    => [
+        A = a -> B C
+        B = a -> C : 3
+    ]
+}
+> Coin \"th\"
+> Coin \"tt\"
+> Paths \"aa\"
+> Paths \"b\"";
+        let engine = Engine::from_str(code.to_owned());
+        assert_eq!(["0.25", "0", "3", "0"], engine.output());
+    }
+
+    #[test]
+    #[should_panic(expected = "Weights need a WFA block")]
+    fn weight_outside_wfa() {
+        Engine::from_str(CODE.replace("A = 0 -> B", "A = 0 -> B : 2"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid Out Statement on line 12")]
+    fn invalid_count() {
+        Engine::from_str(format!("{}\n> ZeroOne accepted abc", CODE));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid Output Syntax")]
+    fn undefined_transition() {
+        Engine::from_str(CODE.to_owned());
+    }
+}
@@ -1,3 +1,3 @@
-mod automata;
+pub mod automata;
 pub mod engine;
-mod parser;
+pub mod parser;
@@ -1,8 +1,11 @@
+use std::fmt;
+
 use self::statement::{Statement, Token};
 
 pub mod statement;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[allow(clippy::enum_variant_names)]
 pub enum Scope {
     GlobalScope,
     BlockScope,
@@ -11,131 +14,149 @@ pub enum Scope {
 
 use Statement::*;
 pub fn parse(code: String) -> Option<Vec<(Statement, Option<Vec<Token>>)>> {
+    let statements = parse_lines(code).ok()?;
+    Some(
+        statements
+            .into_iter()
+            .map(|(_, statement, tokens)| (statement, tokens))
+            .collect(),
+    )
+}
+
+/// Statement with its tokens and its line number, from 1.
+pub type Line = (usize, Statement, Option<Vec<Token>>);
+
+/// Line that could not be parsed, with the statement it looked like if any.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SyntaxError {
+    pub line: usize,
+    pub statement: Option<Statement>,
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.statement {
+            Some(statement) => write!(f, "Invalid {:?} Statement on line {}", statement, self.line),
+            None => write!(f, "Invalid Statement on line {}", self.line),
+        }
+    }
+}
+
+/// Same as [`parse`], each statement coming with its line number, or the
+/// first line that is not valid.
+pub fn parse_lines(code: String) -> Result<Vec<Line>, SyntaxError> {
     use Scope::*;
     let mut code_tokens = Vec::new();
     let mut block_scope = GlobalScope;
     let mut i = 0;
-    for line in code.lines() {
-        let s_type = Statement::match_statement(line);
-        if s_type.is_none() {
-            return None;
-        }
-        let s_type = s_type.unwrap();
+    for (number, line) in code.lines().enumerate() {
+        let number = number + 1;
+        let s_type = Statement::match_statement(line).ok_or(SyntaxError {
+            line: number,
+            statement: None,
+        })?;
+        let error = SyntaxError {
+            line: number,
+            statement: Some(s_type),
+        };
         match s_type {
-            FADef => {
+            FADef | NFADef | MealyDef | MooreDef | PDADef | TMDef | WFADef => {
                 if block_scope == GlobalScope {
                     block_scope = BlockScope;
-                    let token = Statement::parse_statement(line);
-                    if token.is_none() {
-                        return None;
-                    }
-                    code_tokens.push((s_type, token));
+                    let token = Statement::parse_statement(line).ok_or(error)?;
+                    code_tokens.push((number, s_type, Some(token)));
                 } else {
-                    return None;
+                    return Err(error);
                 }
             }
             AlphabetDef => {
                 if block_scope == BlockScope && i == 0 {
                     i += 1;
-                    let token = Statement::parse_statement(line);
-                    if token.is_none() {
-                        return None;
-                    }
-                    code_tokens.push((s_type, token));
+                    let token = Statement::parse_statement(line).ok_or(error)?;
+                    code_tokens.push((number, s_type, Some(token)));
+                } else {
+                    return Err(error);
+                }
+            }
+            StackAlphabetDef => {
+                if block_scope == BlockScope && i == 1 {
+                    let token = Statement::parse_statement(line).ok_or(error)?;
+                    code_tokens.push((number, s_type, Some(token)));
                 } else {
-                    return None;
+                    return Err(error);
                 }
             }
             StateDef => {
                 if block_scope == BlockScope && i == 1 {
                     i += 1;
-                    let token = Statement::parse_statement(line);
-                    if token.is_none() {
-                        return None;
-                    }
-                    code_tokens.push((s_type, token));
+                    let token = Statement::parse_statement(line).ok_or(error)?;
+                    code_tokens.push((number, s_type, Some(token)));
                 } else {
-                    return None;
+                    return Err(error);
                 }
             }
             DeltaDef => {
                 if block_scope == BlockScope && i == 2 {
                     i += 1;
                     block_scope = DeltaScope;
-                    let token = Statement::parse_statement(line);
-                    if token.is_none() {
-                        return None;
-                    }
-                    code_tokens.push((s_type, token));
+                    let token = Statement::parse_statement(line).ok_or(error)?;
+                    code_tokens.push((number, s_type, Some(token)));
                 } else {
-                    return None;
+                    return Err(error);
                 }
             }
             SelfLoop => {
                 if block_scope == DeltaScope {
-                    let token = Statement::parse_statement(line);
-                    if token.is_none() {
-                        return None;
-                    }
-                    code_tokens.push((s_type, token));
+                    let token = Statement::parse_statement(line).ok_or(error)?;
+                    code_tokens.push((number, s_type, Some(token)));
                 } else {
-                    return None;
+                    return Err(error);
                 }
             }
-            Transition => {
+            Transition | MealySelfLoop | MealyTransition | PDATransition | TMTransition => {
                 if block_scope == DeltaScope {
-                    let token = Statement::parse_statement(line);
-                    if token.is_none() {
-                        return None;
-                    }
-                    code_tokens.push((s_type, token));
+                    let token = Statement::parse_statement(line).ok_or(error)?;
+                    code_tokens.push((number, s_type, Some(token)));
                 } else {
-                    return None;
+                    return Err(error);
                 }
             }
             DeltaDefEnd => {
                 if block_scope == DeltaScope && i == 3 {
                     block_scope = BlockScope;
-                    let token = Statement::parse_statement(line);
-                    if token.is_none() {
-                        return None;
-                    }
-                    code_tokens.push((s_type, token));
+                    let token = Statement::parse_statement(line).ok_or(error)?;
+                    code_tokens.push((number, s_type, Some(token)));
                 } else {
-                    return None;
+                    return Err(error);
                 }
             }
             FADefEnd => {
                 if block_scope == BlockScope && i == 3 {
                     block_scope = GlobalScope;
                     i = 0;
-                    let token = Statement::parse_statement(line);
-                    if token.is_none() {
-                        return None;
-                    }
-                    code_tokens.push((s_type, token));
+                    let token = Statement::parse_statement(line).ok_or(error)?;
+                    code_tokens.push((number, s_type, Some(token)));
                 } else {
-                    return None;
+                    return Err(error);
                 }
             }
-            Out => {
+            Out | DerivedDef | RegexDef => {
                 if block_scope == GlobalScope {
-                    let token = Statement::parse_statement(line);
-                    if token.is_none() {
-                        return None;
-                    }
-                    code_tokens.push((s_type, token));
+                    let token = Statement::parse_statement(line).ok_or(error)?;
+                    code_tokens.push((number, s_type, Some(token)));
+                } else {
+                    return Err(error);
                 }
             }
             Comment | Empty => {}
         }
     }
-    Some(code_tokens)
+    Ok(code_tokens)
 }
 
 #[cfg(test)]
 mod parse_tests {
-    use super::{parse, statement::Token::*};
+    use super::{parse, parse_lines, statement::Statement, statement::Token::*};
     #[test]
     fn basic() {
         const CODE: &str = include_str!("../../../a.txt");
@@ -243,4 +264,26 @@ mod parse_tests {
         let _p_tokens = parse(CODE.to_owned());
         // assert_eq!(tokens, p_tokens);
     }
+
+    #[test]
+    fn lines() {
+        let code = "// two blocks\n\nFA A {\n:= 0\n+ (A) {A}\n=> [\n]\n}\n> A \"0\"";
+        let lines: Vec<(usize, Statement)> = parse_lines(code.to_owned())
+            .unwrap()
+            .into_iter()
+            .map(|(line, statement, _)| (line, statement))
+            .collect();
+        assert_eq!((3, Statement::FADef), lines[0]);
+        assert_eq!((9, Statement::Out), lines[6]);
+
+        let error = parse_lines(code.replace("\"0\"", "0 1")).unwrap_err();
+        assert_eq!("Invalid Out Statement on line 9", error.to_string());
+
+        let error = parse_lines(code.replace("=> [\n]", "=> [\nRE B = /0/\n]")).unwrap_err();
+        assert_eq!("Invalid RegexDef Statement on line 7", error.to_string());
+        let error = parse_lines(code.replace("=> [\n]", "=> [\nFA B = minimize A\n]")).unwrap_err();
+        assert_eq!("Invalid DerivedDef Statement on line 7", error.to_string());
+        let error = parse_lines(code.replace("=> [\n]", "=> [\n> A \"0\"\n]")).unwrap_err();
+        assert_eq!("Invalid Out Statement on line 7", error.to_string());
+    }
 }
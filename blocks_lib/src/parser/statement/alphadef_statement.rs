@@ -1,6 +1,12 @@
 use super::{Statement, Token};
+use crate::automata::symbol_class;
 use Token::*;
 
+/// True for an item standing for symbols: a symbol, a range or a class.
+pub(super) fn is_symbols(item: &str) -> bool {
+    item.chars().all(char::is_alphanumeric) || symbol_class(item).is_some()
+}
+
 impl Statement {
     pub fn alphadef_parse_statement(self, statement: &str) -> Option<Vec<Token>> {
         let items: Vec<&str> = statement.trim().split_ascii_whitespace().collect();
@@ -12,7 +18,7 @@ impl Statement {
                     if item == "//" {
                         tokens.push(Operator(item.to_string()));
                         break;
-                    } else if item.chars().all(char::is_alphanumeric) {
+                    } else if is_symbols(item) {
                         tokens.push(Operand(item.to_string()));
                     } else {
                         return None;
@@ -40,4 +46,18 @@ mod alphadef_parse_statement_test {
         assert!(ad_tokens.is_some());
         assert_eq!(tokens, ad_tokens.unwrap());
     }
+
+    #[test]
+    fn classes() {
+        let line = ":= a-z \\d _";
+        assert!(Statement::parse_statement(line).is_none());
+        let tokens = vec![
+            Operator(":=".to_string()),
+            Operand("a-z".to_string()),
+            Operand("\\w".to_string()),
+        ];
+        assert_eq!(Some(tokens), Statement::parse_statement(":= a-z \\w"));
+        assert!(Statement::parse_statement(":= z-a").is_none());
+        assert!(Statement::parse_statement(":= !0").is_none());
+    }
 }
@@ -0,0 +1,109 @@
+use super::{Statement, Token};
+use Token::*;
+
+/// Operations a block can be derived with, e.g. `FA Min = minimize LastOne`
+/// or `FA Both = LastOne & FirstOne`.
+const OPERATIONS: [&str; 12] = [
+    "minimize",
+    "trim",
+    "complete",
+    "complement",
+    "reverse",
+    "star",
+    "plus",
+    "&",
+    "|",
+    "-",
+    "^",
+    ".",
+];
+
+impl Statement {
+    pub fn deriveddef_parse_statement(self, statement: &str) -> Option<Vec<Token>> {
+        let items: Vec<&str> = statement.trim().split_ascii_whitespace().collect();
+        let mut tokens = Vec::with_capacity(items.len());
+        for (i, item) in items.into_iter().enumerate() {
+            match i {
+                0 if item == "FA" => tokens.push(Operator(item.to_string())),
+                1 if item.chars().all(char::is_alphabetic) => {
+                    tokens.push(Operand(item.to_string()))
+                }
+                2 if item == "=" => tokens.push(Operator(item.to_string())),
+                _ if i > 2 => {
+                    if item == "//" {
+                        tokens.push(Operator(item.to_string()));
+                        break;
+                    } else if OPERATIONS.contains(&item) {
+                        tokens.push(Operator(item.to_string()));
+                    } else if item.chars().all(char::is_alphabetic) {
+                        tokens.push(Operand(item.to_string()));
+                    } else {
+                        return None;
+                    }
+                }
+                _ => return None,
+            }
+        }
+        Some(tokens)
+    }
+}
+
+#[cfg(test)]
+mod deriveddef_parse_statement_test {
+    use super::super::{Statement, Token::*};
+    #[test]
+    fn basic() {
+        let line = "FA Min = minimize LastOne // smallest LastOne";
+        let tokens = vec![
+            Operator("FA".to_string()),
+            Operand("Min".to_string()),
+            Operator("=".to_string()),
+            Operator("minimize".to_string()),
+            Operand("LastOne".to_string()),
+            Operator("//".to_string()),
+        ];
+        let ad_tokens = Statement::parse_statement(line);
+        assert!(ad_tokens.is_some());
+        assert_eq!(tokens, ad_tokens.unwrap());
+    }
+
+    #[test]
+    fn binary() {
+        let line = "FA Both = LastOne & FirstOne";
+        let tokens = vec![
+            Operator("FA".to_string()),
+            Operand("Both".to_string()),
+            Operator("=".to_string()),
+            Operand("LastOne".to_string()),
+            Operator("&".to_string()),
+            Operand("FirstOne".to_string()),
+        ];
+        let ad_tokens = Statement::parse_statement(line);
+        assert!(ad_tokens.is_some());
+        assert_eq!(tokens, ad_tokens.unwrap());
+    }
+
+    #[test]
+    fn closure() {
+        let line = "FA Two = reverse LastOne . star FirstOne";
+        let tokens = vec![
+            Operator("FA".to_string()),
+            Operand("Two".to_string()),
+            Operator("=".to_string()),
+            Operator("reverse".to_string()),
+            Operand("LastOne".to_string()),
+            Operator(".".to_string()),
+            Operator("star".to_string()),
+            Operand("FirstOne".to_string()),
+        ];
+        let ad_tokens = Statement::parse_statement(line);
+        assert!(ad_tokens.is_some());
+        assert_eq!(tokens, ad_tokens.unwrap());
+    }
+
+    #[test]
+    fn basic_fails() {
+        let line = "FA Min = minimize Last1";
+        assert!(Statement::parse_statement(line).is_none());
+    }
+}
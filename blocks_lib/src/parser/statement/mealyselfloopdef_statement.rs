@@ -0,0 +1,78 @@
+use super::{Statement, Token, EPSILON, EPSILON_ASCII};
+use Token::*;
+
+impl Statement {
+    /// Self loop writing an output, `@A = 0 1 / x`.
+    pub fn mealyselfloopdef_parse_statement(self, statement: &str) -> Option<Vec<Token>> {
+        let items: Vec<&str> = statement.trim().split_ascii_whitespace().collect();
+        let mut tokens = Vec::with_capacity(items.len());
+        let mut has_output = false;
+        for (i, item) in items.into_iter().enumerate() {
+            match i {
+                0 => {
+                    let state = item.strip_prefix('@')?;
+                    if state.is_empty() || !state.chars().all(char::is_alphanumeric) {
+                        return None;
+                    }
+                    tokens.push(Operator("@".to_string()));
+                    tokens.push(Operand(state.to_string()));
+                }
+                1 if item == "=" => tokens.push(Operator(item.to_string())),
+                _ if i > 1 => {
+                    if item == "//" {
+                        tokens.push(Operator(item.to_string()));
+                        break;
+                    } else if item == "/" && !has_output {
+                        if matches!(tokens.last(), Some(Operator(_))) {
+                            return None;
+                        }
+                        tokens.push(Operator(item.to_string()));
+                    } else if has_output {
+                        return None;
+                    } else if matches!(tokens.last(), Some(Operator(op)) if op == "/") {
+                        has_output = true;
+                        if item == EPSILON_ASCII {
+                            tokens.push(Operand(EPSILON.to_string()));
+                        } else if item.chars().all(char::is_alphanumeric) {
+                            tokens.push(Operand(item.to_string()));
+                        } else {
+                            return None;
+                        }
+                    } else if item.chars().all(char::is_alphanumeric) {
+                        tokens.push(Operand(item.to_string()));
+                    } else {
+                        return None;
+                    }
+                }
+                _ => return None,
+            }
+        }
+        if !has_output {
+            return None;
+        }
+        Some(tokens)
+    }
+}
+
+#[cfg(test)]
+mod mealyselfloopdef_parse_statement_test {
+    use super::super::{Statement, Token::*};
+    #[test]
+    fn basic() {
+        let line = "        @A = 0 1 / eps // ";
+        let tokens = vec![
+            Operator("@".to_string()),
+            Operand("A".to_string()),
+            Operator("=".to_string()),
+            Operand("0".to_string()),
+            Operand("1".to_string()),
+            Operator("/".to_string()),
+            Operand("ε".to_string()),
+            Operator("//".to_string()),
+        ];
+        let ad_tokens = Statement::parse_statement(line);
+        assert!(ad_tokens.is_some());
+        assert_eq!(tokens, ad_tokens.unwrap());
+        assert!(Statement::parse_statement("@A = 0 / x y").is_none());
+    }
+}
@@ -0,0 +1,81 @@
+use super::{Statement, Token, EPSILON, EPSILON_ASCII};
+use Token::*;
+
+impl Statement {
+    /// Transition writing an output, `A = 0 1 / x -> B`. The output is a word
+    /// or `eps` for nothing.
+    pub fn mealytransdef_parse_statement(self, statement: &str) -> Option<Vec<Token>> {
+        let items: Vec<&str> = statement.trim().split_ascii_whitespace().collect();
+        let mut tokens = Vec::with_capacity(items.len());
+        // 0: input symbols, 1: output, 2: next state
+        let mut part = 0;
+        for (i, item) in items.into_iter().enumerate() {
+            match i {
+                0 if item.chars().all(char::is_alphanumeric) => {
+                    tokens.push(Operand(item.to_string()))
+                }
+                1 if item == "=" => tokens.push(Operator(item.to_string())),
+                _ if i > 1 => {
+                    if item == "//" {
+                        tokens.push(Operator(item.to_string()));
+                        break;
+                    } else if (item == "/" && part == 0) || (item == "->" && part == 1) {
+                        if matches!(tokens.last(), Some(Operator(_))) {
+                            return None;
+                        }
+                        part += 1;
+                        tokens.push(Operator(item.to_string()));
+                    } else if part == 1 && matches!(tokens.last(), Some(Operand(_))) {
+                        return None;
+                    } else if part == 1 && item == EPSILON_ASCII {
+                        tokens.push(Operand(EPSILON.to_string()));
+                    } else if item.chars().all(char::is_alphanumeric) {
+                        tokens.push(Operand(item.to_string()));
+                    } else {
+                        return None;
+                    }
+                }
+                _ => return None,
+            }
+        }
+        if part != 2 || matches!(tokens.last(), Some(Operator(op)) if op == "->") {
+            return None;
+        }
+        Some(tokens)
+    }
+}
+
+#[cfg(test)]
+mod mealytransdef_parse_statement_test {
+    use super::super::{Statement, Token::*};
+    #[test]
+    fn basic() {
+        let line = "        A = 0 1 / x -> B // write x";
+        let tokens = vec![
+            Operand("A".to_string()),
+            Operator("=".to_string()),
+            Operand("0".to_string()),
+            Operand("1".to_string()),
+            Operator("/".to_string()),
+            Operand("x".to_string()),
+            Operator("->".to_string()),
+            Operand("B".to_string()),
+            Operator("//".to_string()),
+        ];
+        let ad_tokens = Statement::parse_statement(line);
+        assert!(ad_tokens.is_some());
+        assert_eq!(tokens, ad_tokens.unwrap());
+    }
+
+    #[test]
+    fn basic_fails() {
+        for line in [
+            "A = 0 / x y -> B",
+            "A = 0 / -> B",
+            "A = 0 / x",
+            "A = 0 / x ->",
+        ] {
+            assert!(Statement::parse_statement(line).is_none(), "{}", line);
+        }
+    }
+}
@@ -4,22 +4,51 @@ mod alphadef_statement;
 mod comment_statement;
 mod deltadef_statement;
 mod deltadefend_statement;
+mod deriveddef_statement;
 mod empty_statement;
 mod fadef_statement;
 mod fadefend_statement;
+mod mealydef_statement;
+mod mealyselfloopdef_statement;
+mod mealytransdef_statement;
+mod mooredef_statement;
+mod nfadef_statement;
 mod out_statement;
+mod pdadef_statement;
+mod pdatransdef_statement;
+mod regexdef_statement;
 mod selfloopdef_statement;
+mod stackalphadef_statement;
 mod statedef_statement;
+mod tmdef_statement;
+mod tmtransdef_statement;
 mod transdef_statement;
+mod wfadef_statement;
+
+pub use transdef_statement::EPSILON;
+use transdef_statement::EPSILON_ASCII;
 
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub enum Statement {
     FADef,
+    NFADef,
+    MealyDef,
+    MooreDef,
+    PDADef,
+    TMDef,
+    WFADef,
+    DerivedDef,
+    RegexDef,
     AlphabetDef,
+    StackAlphabetDef,
     StateDef,
     DeltaDef,
     SelfLoop,
     Transition,
+    MealySelfLoop,
+    MealyTransition,
+    PDATransition,
+    TMTransition,
     DeltaDefEnd,
     FADefEnd,
     Out,
@@ -35,6 +64,26 @@ pub enum Token {
 
 impl Statement {
     pub fn match_statement(statement: &str) -> Option<Statement> {
+        let is_derived = statement.split_ascii_whitespace().nth(2) == Some("=");
+        let has_output = statement
+            .split_ascii_whitespace()
+            .take_while(|item| *item != "//")
+            .any(|item| item == "/");
+        // commas show up before the arrow of a PDA transition and after the
+        // one of a TM transition
+        let items: Vec<&str> = statement
+            .split_ascii_whitespace()
+            .take_while(|item| *item != "//")
+            .collect();
+        let arrow = items.iter().position(|item| *item == "->");
+        let has_stack = items[..arrow.unwrap_or(items.len())]
+            .iter()
+            .any(|item| item.contains(','));
+        let has_move =
+            arrow.is_some_and(|arrow| items[arrow..].iter().any(|item| item.contains(',')));
+        // a keyword followed by `=` is a state named like a keyword, as in
+        // `FA = 1 -> B`
+        let is_definition = items.get(1) != Some(&"=");
         let statement = statement.trim();
         let statement = statement.as_bytes();
         let mut i = 0;
@@ -50,12 +99,41 @@ impl Statement {
             i += 1;
         };
         match statement[i] {
-            b'F' if statement.len() > 1
+            b'F' if is_definition
+                && statement.len() > 1
                 && statement[i + 1] == b'A'
                 && statement.len() > 2
                 && (statement[i + 2] == b' ' || statement[i + 2] == b'\t') =>
             {
-                Some(Statement::FADef)
+                if is_derived {
+                    Some(Statement::DerivedDef)
+                } else {
+                    Some(Statement::FADef)
+                }
+            }
+            b'N' if is_definition
+                && statement.len() > 3
+                && statement[i + 1] == b'F'
+                && statement[i + 2] == b'A'
+                && (statement[i + 3] == b' ' || statement[i + 3] == b'\t') =>
+            {
+                Some(Statement::NFADef)
+            }
+            b'M' if is_definition && is_keyword(&statement[i..], b"MEALY") => {
+                Some(Statement::MealyDef)
+            }
+            b'M' if is_definition && is_keyword(&statement[i..], b"MOORE") => {
+                Some(Statement::MooreDef)
+            }
+            b'P' if is_definition && is_keyword(&statement[i..], b"PDA") => Some(Statement::PDADef),
+            b'T' if is_definition && is_keyword(&statement[i..], b"TM") => Some(Statement::TMDef),
+            b'W' if is_definition && is_keyword(&statement[i..], b"WFA") => Some(Statement::WFADef),
+            b'R' if is_definition
+                && statement.len() > 2
+                && statement[i + 1] == b'E'
+                && (statement[i + 2] == b' ' || statement[i + 2] == b'\t') =>
+            {
+                Some(Statement::RegexDef)
             }
             b':' if statement.len() > 1
                 && statement[i + 1] == b'='
@@ -64,6 +142,8 @@ impl Statement {
             {
                 Some(Statement::AlphabetDef)
             }
+            b'$' if is_keyword(&statement[i..], b"$=") => Some(Statement::StackAlphabetDef),
+            b'>' if is_keyword(&statement[i..], b">?") => Some(Statement::Out),
             b'>' if statement.len() > 1 && statement[i + 1] == b' '
                 || statement[i + 1] == b'\t' =>
             {
@@ -85,17 +165,24 @@ impl Statement {
                 && statement[i + 1].is_ascii_alphanumeric()
                 && (statement[i + 1] != b' ' || statement[i + 1] != b'\t') =>
             {
-                Some(Statement::SelfLoop)
+                if has_output {
+                    Some(Statement::MealySelfLoop)
+                } else {
+                    Some(Statement::SelfLoop)
+                }
             }
+            b if b.is_ascii_alphanumeric() && has_stack => Some(Statement::PDATransition),
+            b if b.is_ascii_alphanumeric() && has_move => Some(Statement::TMTransition),
+            b if b.is_ascii_alphanumeric() && has_output => Some(Statement::MealyTransition),
             b if b.is_ascii_alphanumeric() => Some(Statement::Transition),
             b']' => {
-                if statement.len() > 1 && (statement[i + 1] != b' ' || statement[i + 1] != b'\t') {
+                if statement.len() > 1 && statement[i + 1] != b' ' && statement[i + 1] != b'\t' {
                     return None;
                 }
                 Some(Statement::DeltaDefEnd)
             }
             b'}' => {
-                if statement.len() > 1 && (statement[i + 1] != b' ' || statement[i + 1] != b'\t') {
+                if statement.len() > 1 && statement[i + 1] != b' ' && statement[i + 1] != b'\t' {
                     return None;
                 }
                 Some(Statement::FADefEnd)
@@ -105,18 +192,27 @@ impl Statement {
         }
     }
     pub fn parse_statement(statement: &str) -> Option<Vec<Token>> {
-        let s = Self::match_statement(statement);
-        if s.is_none() {
-            return None;
-        }
-        let s = s.unwrap();
+        let s = Self::match_statement(statement)?;
         match s {
             Statement::FADef => s.fadef_parse_statement(statement),
+            Statement::NFADef => s.nfadef_parse_statement(statement),
+            Statement::MealyDef => s.mealydef_parse_statement(statement),
+            Statement::MooreDef => s.mooredef_parse_statement(statement),
+            Statement::PDADef => s.pdadef_parse_statement(statement),
+            Statement::TMDef => s.tmdef_parse_statement(statement),
+            Statement::WFADef => s.wfadef_parse_statement(statement),
+            Statement::DerivedDef => s.deriveddef_parse_statement(statement),
+            Statement::RegexDef => s.regexdef_parse_statement(statement),
             Statement::AlphabetDef => s.alphadef_parse_statement(statement),
+            Statement::StackAlphabetDef => s.stackalphadef_parse_statement(statement),
             Statement::StateDef => s.statedef_parse_statement(statement),
             Statement::DeltaDef => s.deltadef_parse_statement(statement),
             Statement::SelfLoop => s.selfloopdef_parse_statement(statement),
             Statement::Transition => s.transdef_parse_statement(statement),
+            Statement::MealySelfLoop => s.mealyselfloopdef_parse_statement(statement),
+            Statement::MealyTransition => s.mealytransdef_parse_statement(statement),
+            Statement::PDATransition => s.pdatransdef_parse_statement(statement),
+            Statement::TMTransition => s.tmtransdef_parse_statement(statement),
             Statement::DeltaDefEnd => s.deltadefend_parse_statement(statement),
             Statement::FADefEnd => s.fadefend_parse_statement(statement),
             Statement::Out => s.out_parse_statement(statement),
@@ -126,6 +222,13 @@ impl Statement {
     }
 }
 
+/// True when `statement` starts with the `keyword` of a block followed by
+/// whitespace, like `MEALY Encoder {`.
+fn is_keyword(statement: &[u8], keyword: &[u8]) -> bool {
+    statement.starts_with(keyword)
+        && matches!(statement.get(keyword.len()), Some(b' ') | Some(b'\t'))
+}
+
 #[cfg(test)]
 mod match_statement_tests {
     use super::Statement;
@@ -161,17 +264,66 @@ FA FirstOne {
             Statement::FADefEnd,
             Statement::Out,
         ];
-        let mut i = 0;
         let lines = code.lines();
-        for line in lines {
+        for (i, line) in lines.enumerate() {
             let statement = Statement::match_statement(line);
-            assert_eq!(true, statement.is_some());
+            assert!(statement.is_some());
             let statement = statement.unwrap();
             assert_eq!(STATEMENTS[i], statement);
-            i += 1;
+        }
+    }
+    #[test]
+    fn keyword_states() {
+        for (line, statement) in [
+            ("FA = 0 1 -> NFA", Statement::Transition),
+            ("NFA = 0 -> B C", Statement::Transition),
+            ("MEALY = 0 / 1 -> B", Statement::MealyTransition),
+            ("MOORE = 0 -> B", Statement::Transition),
+            ("PDA = a, Z / Z -> B", Statement::PDATransition),
+            ("TM = a -> B, b, R", Statement::TMTransition),
+            ("TM Increment {", Statement::TMDef),
+            ("WFA = h -> B : 0.5", Statement::Transition),
+            ("RE = 1 -> B", Statement::Transition),
+            ("RE Copy = /01/", Statement::RegexDef),
+        ] {
+            assert_eq!(
+                Some(statement),
+                Statement::match_statement(line),
+                "{}",
+                line
+            );
         }
     }
 }
+#[cfg(test)]
+mod mealy_match_statement_tests {
+    use super::Statement;
+    #[test]
+    fn basic() {
+        let code = "MEALY Changes {
+    + (Z) O
+    => [
+        @Z = 0 / 0
+        Z = 1 / 1 -> O // slash in a comment /
+        @O = 1 // slash in a comment /
+    ]
+}";
+        const STATEMENTS: [Statement; 8] = [
+            Statement::MealyDef,
+            Statement::StateDef,
+            Statement::DeltaDef,
+            Statement::MealySelfLoop,
+            Statement::MealyTransition,
+            Statement::SelfLoop,
+            Statement::DeltaDefEnd,
+            Statement::FADefEnd,
+        ];
+        for (i, line) in code.lines().enumerate() {
+            assert_eq!(Some(STATEMENTS[i]), Statement::match_statement(line));
+        }
+    }
+}
+
 #[cfg(test)]
 mod parse_statement_tests {
     use crate::parser::statement::Token::{self, *};
@@ -240,14 +392,11 @@ FA FirstOne {
             vec![Operator("]".to_string())],
             vec![Operator("}".to_string())],
         ];
-        let mut i = 0;
         let lines = code.lines();
-        for line in lines {
-            // dbg!(Statement::parse_statement(line));
+        for (i, line) in lines.enumerate() {
             let p_tokens = Statement::parse_statement(line);
             assert!(p_tokens.is_some());
             assert_eq!(tokens[i], p_tokens.unwrap());
-            i += 1;
         }
     }
 }
@@ -3,15 +3,29 @@ use Token::*;
 
 impl Statement {
     pub fn out_parse_statement(self, statement: &str) -> Option<Vec<Token>> {
-        let items: Vec<&str> = statement.trim().split_ascii_whitespace().collect();
+        let items = items(statement.trim());
         let mut tokens = Vec::with_capacity(items.len());
         for (i, item) in items.into_iter().enumerate() {
             match i {
-                0 if item == ">" => tokens.push(Operator(item.to_string())),
+                0 if item == ">" || item == ">?" => tokens.push(Operator(item.to_string())),
                 1 if item.chars().all(char::is_alphabetic) => {
                     tokens.push(Operand(item.to_string()))
                 }
                 2 if is_string(item) => tokens.push(Operand(from_string(item.to_string()))),
+                2 if tokens[0] == Operator(">".to_string())
+                    && (item == "accepted" || item == "rejected" || item == "regex") =>
+                {
+                    tokens.push(Operator(item.to_string()))
+                }
+                3 if is_words(&tokens[2]) && is_count(item) => {
+                    tokens.push(Operand(item.to_string()))
+                }
+                3 if tokens[0] == Operator(">".to_string())
+                    && matches!(tokens[2], Operand(_))
+                    && item == "trace" =>
+                {
+                    tokens.push(Operator(item.to_string()))
+                }
                 _ => {
                     if item == "//" {
                         tokens.push(Operator(item.to_string()));
@@ -22,19 +36,143 @@ impl Statement {
                 }
             }
         }
+        // the words listed need a count, nothing else takes one
+        if tokens.get(2).is_some_and(is_words) && !matches!(tokens.get(3), Some(Operand(_))) {
+            return None;
+        }
         Some(tokens)
     }
 }
 
+/// True for the operators listing words of a language, `accepted` and
+/// `rejected`, which are followed by the number of words.
+fn is_words(token: &Token) -> bool {
+    *token == Operator("accepted".to_string()) || *token == Operator("rejected".to_string())
+}
+
+/// Whitespace separated items, a string being one item even if it holds
+/// whitespace, like `"login read"`.
+fn items(statement: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut rest = statement;
+    while !rest.is_empty() {
+        let end = if let Some(string) = rest.strip_prefix('"') {
+            string.find('"').map_or(rest.len(), |i| i + 2)
+        } else {
+            rest.find(|c: char| c.is_ascii_whitespace())
+                .unwrap_or(rest.len())
+        };
+        items.push(&rest[..end]);
+        rest = rest[end..].trim_start();
+    }
+    items
+}
+
+/// Strings hold symbols, or tokens separated by whitespace or commas.
 fn is_string(item: &str) -> bool {
-    let r = item.starts_with('"')
+    let r = item.len() >= 2
+        && item.starts_with('"')
         && item.ends_with('"')
-        && item[1..item.len() - 1].chars().all(char::is_alphanumeric);
+        && item[1..item.len() - 1]
+            .chars()
+            .all(|c| c.is_alphanumeric() || c.is_whitespace() || c == ',' || c == '_');
     r
 }
 
+/// Number of words to list, which must fit a `usize`.
+fn is_count(item: &str) -> bool {
+    item.chars().all(|c| c.is_ascii_digit()) && item.parse::<usize>().is_ok()
+}
+
 fn from_string(mut item: String) -> String {
     item.remove(0);
     item.remove(item.len() - 1);
     item
 }
+
+#[cfg(test)]
+mod out_parse_statement_test {
+    use super::super::{Statement, Token::*};
+    #[test]
+    fn basic() {
+        let line = "> LastOne \"1001\" // run";
+        let tokens = vec![
+            Operator(">".to_string()),
+            Operand("LastOne".to_string()),
+            Operand("1001".to_string()),
+            Operator("//".to_string()),
+        ];
+        let ad_tokens = Statement::parse_statement(line);
+        assert!(ad_tokens.is_some());
+        assert_eq!(tokens, ad_tokens.unwrap());
+    }
+
+    #[test]
+    fn trace() {
+        let line = "> Increment \"1011\" trace";
+        let tokens = vec![
+            Operator(">".to_string()),
+            Operand("Increment".to_string()),
+            Operand("1011".to_string()),
+            Operator("trace".to_string()),
+        ];
+        assert_eq!(Some(tokens), Statement::parse_statement(line));
+        assert!(Statement::parse_statement("> Increment accepted trace").is_none());
+    }
+
+    #[test]
+    fn words() {
+        let line = "> LastOne accepted 10";
+        let tokens = vec![
+            Operator(">".to_string()),
+            Operand("LastOne".to_string()),
+            Operator("accepted".to_string()),
+            Operand("10".to_string()),
+        ];
+        let ad_tokens = Statement::parse_statement(line);
+        assert!(ad_tokens.is_some());
+        assert_eq!(tokens, ad_tokens.unwrap());
+        assert!(Statement::parse_statement("> LastOne \"1001\" 10").is_none());
+        assert!(Statement::parse_statement("> LastOne accepted abc").is_none());
+        assert!(Statement::parse_statement("> LastOne accepted 99999999999999999999999").is_none());
+        assert!(Statement::parse_statement("> LastOne accepted").is_none());
+        assert!(Statement::parse_statement("> LastOne rejected // all").is_none());
+        assert!(Statement::parse_statement("> LastOne regex 10").is_none());
+        assert_eq!(
+            Some(vec![
+                Operator(">".to_string()),
+                Operand("LastOne".to_string()),
+                Operator("regex".to_string()),
+            ]),
+            Statement::parse_statement("> LastOne regex")
+        );
+    }
+
+    #[test]
+    fn explain() {
+        let line = "  >? LastOne \"1001\" // why";
+        let tokens = vec![
+            Operator(">?".to_string()),
+            Operand("LastOne".to_string()),
+            Operand("1001".to_string()),
+            Operator("//".to_string()),
+        ];
+        assert_eq!(Some(tokens), Statement::parse_statement(line));
+        assert!(Statement::parse_statement(">? LastOne regex").is_none());
+        assert!(Statement::parse_statement(">? LastOne \"1001\" trace").is_none());
+    }
+
+    #[test]
+    fn tokens() {
+        let line = "> Session \"login, read  logout\" // tokens";
+        let tokens = vec![
+            Operator(">".to_string()),
+            Operand("Session".to_string()),
+            Operand("login, read  logout".to_string()),
+            Operator("//".to_string()),
+        ];
+        assert_eq!(Some(tokens), Statement::parse_statement(line));
+        assert!(Statement::parse_statement("> Session \"login read").is_none());
+        assert!(Statement::parse_statement("> Session \"login; read\"").is_none());
+    }
+}
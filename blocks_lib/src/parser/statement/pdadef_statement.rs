@@ -0,0 +1,43 @@
+use super::{Statement, Token};
+
+impl Statement {
+    pub fn pdadef_parse_statement(self, statement: &str) -> Option<Vec<Token>> {
+        let items: Vec<&str> = statement.trim().split_ascii_whitespace().collect();
+        let mut tokens = Vec::with_capacity(items.len());
+        for (i, item) in items.into_iter().enumerate() {
+            match i {
+                0 if item == "PDA" => tokens.push(Token::Operator(item.to_owned())),
+                1 if item.chars().all(char::is_alphabetic) => {
+                    tokens.push(Token::Operand(item.to_owned()))
+                }
+                2 if item == "{" => tokens.push(Token::Operator(item.to_owned())),
+                3 if item == "//" => {
+                    tokens.push(Token::Operator(item.to_owned()));
+                    break;
+                }
+                _ => return None,
+            }
+        }
+        Some(tokens)
+    }
+}
+
+#[cfg(test)]
+mod pdadef_parse_statement_tests {
+    use super::{Statement, Token};
+
+    #[test]
+    fn basic() {
+        let line = "PDA Balanced { // as many a as b";
+        let tokens = vec![
+            Token::Operator("PDA".to_string()),
+            Token::Operand("Balanced".to_string()),
+            Token::Operator("{".to_string()),
+            Token::Operator("//".to_string()),
+        ];
+        let pda_tokens = Statement::parse_statement(line);
+        assert!(pda_tokens.is_some());
+        assert_eq!(tokens, pda_tokens.unwrap());
+        assert!(Statement::parse_statement("PDA Balanced { x").is_none());
+    }
+}
@@ -0,0 +1,86 @@
+use super::{Statement, Token, EPSILON, EPSILON_ASCII};
+use Token::*;
+
+impl Statement {
+    /// Transition of a PDA block, `A = a, X / YX -> B`: read `a`, pop `X`,
+    /// push `YX` with `Y` on top and go to `B`. Any of `a`, `X` and `YX` can
+    /// be `eps` to read, pop or push nothing.
+    pub fn pdatransdef_parse_statement(self, statement: &str) -> Option<Vec<Token>> {
+        let statement = statement.replacen(',', " , ", 1);
+        let items: Vec<&str> = statement.trim().split_ascii_whitespace().collect();
+        let items = match items.iter().position(|item| *item == "//") {
+            Some(comment) => &items[..=comment],
+            None => &items[..],
+        };
+        // state, input, stack top, pushed word and next state around the
+        // separators, the comment is optional
+        let shape = ["=", ",", "/", "->"];
+        if items.len() < 9 || items.len() > 10 || (items.len() == 10 && items[9] != "//") {
+            return None;
+        }
+        let mut tokens = Vec::with_capacity(items.len());
+        for (i, item) in items.iter().enumerate() {
+            let is_symbol = item.chars().count() == 1 && item.chars().all(char::is_alphanumeric);
+            let is_word = !item.is_empty() && item.chars().all(char::is_alphanumeric);
+            match i {
+                0 | 8 if is_word => tokens.push(Operand(item.to_string())),
+                1 | 3 | 5 | 7 if *item == shape[i / 2] => tokens.push(Operator(item.to_string())),
+                2 | 4 | 6 if *item == EPSILON_ASCII || *item == EPSILON => {
+                    tokens.push(Operand(EPSILON.to_string()))
+                }
+                2 | 4 if is_symbol => tokens.push(Operand(item.to_string())),
+                6 if is_word => tokens.push(Operand(item.to_string())),
+                9 => tokens.push(Operator(item.to_string())),
+                _ => return None,
+            }
+        }
+        Some(tokens)
+    }
+}
+
+#[cfg(test)]
+mod pdatransdef_parse_statement_test {
+    use super::super::{Statement, Token::*};
+    #[test]
+    fn basic() {
+        let tokens = vec![
+            Operand("A".to_string()),
+            Operator("=".to_string()),
+            Operand("a".to_string()),
+            Operator(",".to_string()),
+            Operand("X".to_string()),
+            Operator("/".to_string()),
+            Operand("YX".to_string()),
+            Operator("->".to_string()),
+            Operand("B".to_string()),
+        ];
+        for line in ["    A = a, X / YX -> B", "A = a , X / YX -> B"] {
+            assert_eq!(Some(tokens.clone()), Statement::parse_statement(line));
+        }
+    }
+
+    #[test]
+    fn epsilon() {
+        let line = "    A = eps, Z / eps -> B // pop the bottom";
+        let tokens = vec![
+            Operand("A".to_string()),
+            Operator("=".to_string()),
+            Operand("ε".to_string()),
+            Operator(",".to_string()),
+            Operand("Z".to_string()),
+            Operator("/".to_string()),
+            Operand("ε".to_string()),
+            Operator("->".to_string()),
+            Operand("B".to_string()),
+            Operator("//".to_string()),
+        ];
+        assert_eq!(Some(tokens), Statement::parse_statement(line));
+    }
+
+    #[test]
+    fn basic_fails() {
+        for line in ["A = a, XY / X -> B", "A = a, X / X", "A = a, X / X -> B C"] {
+            assert!(Statement::parse_statement(line).is_none(), "{}", line);
+        }
+    }
+}
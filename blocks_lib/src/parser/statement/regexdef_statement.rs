@@ -0,0 +1,64 @@
+use super::{Statement, Token};
+use Token::*;
+
+impl Statement {
+    pub fn regexdef_parse_statement(self, statement: &str) -> Option<Vec<Token>> {
+        let mut tokens = Vec::with_capacity(5);
+        let rest = statement.trim().strip_prefix("RE")?.trim_start();
+        tokens.push(Operator("RE".to_string()));
+
+        let name: String = rest.chars().take_while(|c| c.is_alphabetic()).collect();
+        if name.is_empty() {
+            return None;
+        }
+        let rest = rest[name.len()..].trim_start().strip_prefix('=')?;
+        tokens.push(Operand(name));
+        tokens.push(Operator("=".to_string()));
+
+        // the pattern runs up to the next unescaped `/`
+        let rest = rest.trim_start().strip_prefix('/')?;
+        let mut escaped = false;
+        let end = rest.char_indices().find_map(|(i, c)| {
+            let end = c == '/' && !escaped;
+            escaped = c == '\\' && !escaped;
+            end.then_some(i)
+        })?;
+        tokens.push(Operand(rest[..end].to_string()));
+
+        let rest = rest[end + 1..].trim();
+        if rest.starts_with("//") {
+            tokens.push(Operator("//".to_string()));
+        } else if !rest.is_empty() {
+            return None;
+        }
+        Some(tokens)
+    }
+}
+
+#[cfg(test)]
+mod regexdef_parse_statement_test {
+    use super::super::{Statement, Token::*};
+    #[test]
+    fn basic() {
+        let line = "RE LastOne = /(0|1)*1/ // ending with one";
+        let tokens = vec![
+            Operator("RE".to_string()),
+            Operand("LastOne".to_string()),
+            Operator("=".to_string()),
+            Operand("(0|1)*1".to_string()),
+            Operator("//".to_string()),
+        ];
+        let ad_tokens = Statement::parse_statement(line);
+        assert!(ad_tokens.is_some());
+        assert_eq!(tokens, ad_tokens.unwrap());
+    }
+
+    #[test]
+    fn escaped_slash() {
+        let line = "  RE Path = /a\\/b/";
+        let tokens = Statement::parse_statement(line).unwrap();
+        assert_eq!(Operand("a\\/b".to_string()), tokens[3]);
+        assert!(Statement::parse_statement("RE Path = /ab").is_none());
+        assert!(Statement::parse_statement("RE Path = /ab/ c").is_none());
+    }
+}
@@ -1,4 +1,7 @@
-use super::{Statement, Token};
+use super::{
+    transdef_statement::{is_transition_symbols, is_weight},
+    Statement, Token,
+};
 use Token::*;
 
 impl Statement {
@@ -12,7 +15,7 @@ impl Statement {
                     if it[0] != '@' {
                         return None;
                     }
-                    if !it[1..].into_iter().all(|c| char::is_alphanumeric(*c)) {
+                    if !it[1..].iter().all(|c| char::is_alphanumeric(*c)) {
                         return None;
                     }
                     tokens.push(Operator(item.get(0..1).unwrap().to_string()));
@@ -20,10 +23,18 @@ impl Statement {
                 }
                 1 if item == "=" => tokens.push(Operator(item.to_string())),
                 _ => {
+                    let after_colon = matches!(tokens.last(), Some(Operator(op)) if op == ":");
                     if item == "//" {
                         tokens.push(Operator(item.to_string()));
                         break;
-                    } else if item.chars().all(char::is_alphanumeric) {
+                    } else if item == ":" && matches!(tokens.last(), Some(Operand(_))) {
+                        tokens.push(Operator(item.to_string()));
+                    } else if after_colon && is_weight(item) {
+                        tokens.push(Operand(item.to_string()));
+                    } else if after_colon || tokens.contains(&Operator(":".to_string())) {
+                        // nothing but a comment after the weight
+                        return None;
+                    } else if is_transition_symbols(item) {
                         tokens.push(Operand(item.to_string()));
                     } else {
                         return None;
@@ -53,4 +64,18 @@ mod selfloopdef_parse_statement_test {
         assert!(ad_tokens.is_some());
         assert_eq!(tokens, ad_tokens.unwrap());
     }
+
+    #[test]
+    fn weight() {
+        let line = "@A = 0 : 2";
+        let tokens = vec![
+            Operator("@".to_string()),
+            Operand("A".to_string()),
+            Operator("=".to_string()),
+            Operand("0".to_string()),
+            Operator(":".to_string()),
+            Operand("2".to_string()),
+        ];
+        assert_eq!(Some(tokens), Statement::parse_statement(line));
+    }
 }
@@ -0,0 +1,61 @@
+use super::{Statement, Token};
+use Token::*;
+
+impl Statement {
+    /// Stack alphabet of a PDA block or tape alphabet of a TM block, like
+    /// `$= (Z) X`. The symbol in parentheses is the one on the stack at the
+    /// start, which is empty otherwise, or the blank, `_` otherwise.
+    pub fn stackalphadef_parse_statement(self, statement: &str) -> Option<Vec<Token>> {
+        let items: Vec<&str> = statement.trim().split_ascii_whitespace().collect();
+        let mut tokens = Vec::with_capacity(items.len());
+        let mut is_marked = false;
+        for (i, item) in items.into_iter().enumerate() {
+            let symbol = match item.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+                Some(symbol) if !is_marked => {
+                    is_marked = true;
+                    symbol
+                }
+                Some(_) => return None,
+                None => item,
+            };
+            match i {
+                0 if item == "$=" => tokens.push(Operator(item.to_string())),
+                _ => {
+                    if item == "//" {
+                        tokens.push(Operator(item.to_string()));
+                        break;
+                    } else if symbol.chars().count() == 1
+                        && symbol.chars().all(|c| c.is_alphanumeric() || c == '_')
+                    {
+                        tokens.push(Operand(item.to_string()));
+                    } else {
+                        return None;
+                    }
+                }
+            }
+        }
+        Some(tokens)
+    }
+}
+
+#[cfg(test)]
+mod stackalphadef_parse_statement_test {
+    use super::super::{Statement, Token::*};
+    #[test]
+    fn basic() {
+        let line = "    $= (Z) X // bottom and counter";
+        let tokens = vec![
+            Operator("$=".to_string()),
+            Operand("(Z)".to_string()),
+            Operand("X".to_string()),
+            Operator("//".to_string()),
+        ];
+        let ad_tokens = Statement::parse_statement(line);
+        assert!(ad_tokens.is_some());
+        assert_eq!(tokens, ad_tokens.unwrap());
+        assert!(Statement::parse_statement("$= ZX").is_none());
+        assert!(Statement::parse_statement("$= _ X").is_some());
+        assert!(Statement::parse_statement("$= (Z) (X)").is_none());
+        assert!(Statement::parse_statement("$= (ZX)").is_none());
+    }
+}
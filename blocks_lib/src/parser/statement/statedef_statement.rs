@@ -14,7 +14,12 @@ impl Statement {
                         break;
                     } else if item.chars().all(|c| {
                         // TODO implement state tokens
-                        char::is_alphanumeric(c) || c == '(' || c == ')' || c == '{' || c == '}'
+                        char::is_alphanumeric(c)
+                            || c == '('
+                            || c == ')'
+                            || c == '{'
+                            || c == '}'
+                            || c == ':'
                     }) {
                         tokens.push(Operand(item.to_string()));
                     } else {
@@ -43,4 +48,15 @@ mod statedef_parse_statement_test {
         assert!(ad_tokens.is_some());
         assert_eq!(tokens, ad_tokens.unwrap());
     }
+
+    #[test]
+    fn outputs() {
+        let line = "    + (A:0) B:1";
+        let tokens = vec![
+            Operator("+".to_string()),
+            Operand("(A:0)".to_string()),
+            Operand("B:1".to_string()),
+        ];
+        assert_eq!(Some(tokens), Statement::parse_statement(line));
+    }
 }
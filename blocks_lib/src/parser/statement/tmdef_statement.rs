@@ -0,0 +1,43 @@
+use super::{Statement, Token};
+
+impl Statement {
+    pub fn tmdef_parse_statement(self, statement: &str) -> Option<Vec<Token>> {
+        let items: Vec<&str> = statement.trim().split_ascii_whitespace().collect();
+        let mut tokens = Vec::with_capacity(items.len());
+        for (i, item) in items.into_iter().enumerate() {
+            match i {
+                0 if item == "TM" => tokens.push(Token::Operator(item.to_owned())),
+                1 if item.chars().all(char::is_alphabetic) => {
+                    tokens.push(Token::Operand(item.to_owned()))
+                }
+                2 if item == "{" => tokens.push(Token::Operator(item.to_owned())),
+                3 if item == "//" => {
+                    tokens.push(Token::Operator(item.to_owned()));
+                    break;
+                }
+                _ => return None,
+            }
+        }
+        Some(tokens)
+    }
+}
+
+#[cfg(test)]
+mod tmdef_parse_statement_tests {
+    use super::{Statement, Token};
+
+    #[test]
+    fn basic() {
+        let line = "TM Increment { // adds one";
+        let tokens = vec![
+            Token::Operator("TM".to_string()),
+            Token::Operand("Increment".to_string()),
+            Token::Operator("{".to_string()),
+            Token::Operator("//".to_string()),
+        ];
+        let tm_tokens = Statement::parse_statement(line);
+        assert!(tm_tokens.is_some());
+        assert_eq!(tokens, tm_tokens.unwrap());
+        assert!(Statement::parse_statement("TM Increment { x").is_none());
+    }
+}
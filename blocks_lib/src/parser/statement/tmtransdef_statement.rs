@@ -0,0 +1,86 @@
+use super::{Statement, Token};
+use Token::*;
+
+/// Head moves of a TM transition: left, right or stay.
+const MOVES: [&str; 3] = ["L", "R", "S"];
+
+impl Statement {
+    /// Transition of a TM block, `A = a b -> B, x, R`: on `a` or `b` write
+    /// `x`, move the head right and go to `B`.
+    pub fn tmtransdef_parse_statement(self, statement: &str) -> Option<Vec<Token>> {
+        let statement = statement.replace(',', " , ");
+        let items: Vec<&str> = statement.trim().split_ascii_whitespace().collect();
+        let mut tokens = Vec::with_capacity(items.len());
+        let arrow = items.iter().position(|item| *item == "->")?;
+        for (i, item) in items.iter().enumerate() {
+            let is_symbol = item.chars().count() == 1 && is_tape_symbol(item);
+            match i {
+                0 if is_state(item) => tokens.push(Operand(item.to_string())),
+                1 if *item == "=" => tokens.push(Operator(item.to_string())),
+                _ if i > 1 && i < arrow && is_symbol => tokens.push(Operand(item.to_string())),
+                _ if i == arrow && i > 2 => tokens.push(Operator(item.to_string())),
+                _ if i == arrow + 1 && is_state(item) => tokens.push(Operand(item.to_string())),
+                _ if (i == arrow + 2 || i == arrow + 4) && *item == "," => {
+                    tokens.push(Operator(item.to_string()))
+                }
+                _ if i == arrow + 3 && is_symbol => tokens.push(Operand(item.to_string())),
+                _ if i == arrow + 5 && MOVES.contains(item) => {
+                    tokens.push(Operand(item.to_string()))
+                }
+                _ if i == arrow + 6 && *item == "//" => {
+                    tokens.push(Operator(item.to_string()));
+                    break;
+                }
+                _ => return None,
+            }
+        }
+        if tokens.len() < arrow + 6 {
+            return None;
+        }
+        Some(tokens)
+    }
+}
+
+fn is_state(item: &str) -> bool {
+    item.chars().all(char::is_alphanumeric)
+}
+
+fn is_tape_symbol(item: &str) -> bool {
+    item.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tmtransdef_parse_statement_test {
+    use super::super::{Statement, Token::*};
+    #[test]
+    fn basic() {
+        let tokens = vec![
+            Operand("A".to_string()),
+            Operator("=".to_string()),
+            Operand("0".to_string()),
+            Operand("_".to_string()),
+            Operator("->".to_string()),
+            Operand("B".to_string()),
+            Operator(",".to_string()),
+            Operand("1".to_string()),
+            Operator(",".to_string()),
+            Operand("R".to_string()),
+        ];
+        for line in ["    A = 0 _ -> B, 1, R", "A = 0 _ -> B , 1,R"] {
+            assert_eq!(Some(tokens.clone()), Statement::parse_statement(line));
+        }
+    }
+
+    #[test]
+    fn basic_fails() {
+        for line in [
+            "A = 0 -> B, 1",
+            "A = -> B, 1, R",
+            "A = 0 -> B, 1, X",
+            "A = 0 -> B, 12, R",
+            "A = 0 -> B, 1, R, L",
+        ] {
+            assert!(Statement::parse_statement(line).is_none(), "{}", line);
+        }
+    }
+}
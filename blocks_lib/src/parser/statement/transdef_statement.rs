@@ -1,6 +1,21 @@
-use super::{Statement, Token};
+use super::{alphadef_statement::is_symbols, Statement, Token};
 use Token::*;
 
+/// Operand used for ε-moves, `eps` is accepted as its ASCII spelling.
+pub const EPSILON: &str = "ε";
+pub(super) const EPSILON_ASCII: &str = "eps";
+
+/// True for the symbols of a transition, which may be negated like `!0` to
+/// stand for every other symbol of the alphabet.
+pub(super) fn is_transition_symbols(item: &str) -> bool {
+    is_symbols(item.strip_prefix('!').unwrap_or(item))
+}
+
+/// True for the weight ending a transition of a WFA block, `A = 0 -> B : 0.5`.
+pub(super) fn is_weight(item: &str) -> bool {
+    item.parse::<f64>().is_ok()
+}
+
 impl Statement {
     pub fn transdef_parse_statement(self, statement: &str) -> Option<Vec<Token>> {
         let items: Vec<&str> = statement.trim().split_ascii_whitespace().collect();
@@ -12,12 +27,26 @@ impl Statement {
                 }
                 1 if item == "=" => tokens.push(Operator(item.to_string())),
                 _ => {
-                    if item == "->" {
-                        tokens.push(Operator(item.to_string()));
-                    } else if item == "//" {
+                    let after_colon = matches!(tokens.last(), Some(Operator(op)) if op == ":");
+                    if item == "//" {
                         tokens.push(Operator(item.to_string()));
                         break;
-                    } else if item.chars().all(char::is_alphanumeric) {
+                    } else if item == "->"
+                        || (item == ":" && matches!(tokens.last(), Some(Operand(_))))
+                    {
+                        tokens.push(Operator(item.to_string()));
+                    } else if after_colon && is_weight(item) {
+                        tokens.push(Operand(item.to_string()));
+                    } else if after_colon || tokens.contains(&Operator(":".to_string())) {
+                        // nothing but a comment after the weight
+                        return None;
+                    } else if item == EPSILON_ASCII && !tokens.contains(&Operator("->".to_string()))
+                    {
+                        tokens.push(Operand(EPSILON.to_string()));
+                    } else if item.chars().all(char::is_alphanumeric)
+                        || (!tokens.contains(&Operator("->".to_string()))
+                            && is_transition_symbols(item))
+                    {
                         tokens.push(Operand(item.to_string()));
                     } else {
                         return None;
@@ -48,4 +77,65 @@ mod transdef_parse_statement_test {
         assert!(ad_tokens.is_some());
         assert_eq!(tokens, ad_tokens.unwrap());
     }
+
+    #[test]
+    fn weight() {
+        let line = "    A = 0 -> B : 0.5 // half";
+        let tokens = vec![
+            Operand("A".to_string()),
+            Operator("=".to_string()),
+            Operand("0".to_string()),
+            Operator("->".to_string()),
+            Operand("B".to_string()),
+            Operator(":".to_string()),
+            Operand("0.5".to_string()),
+            Operator("//".to_string()),
+        ];
+        assert_eq!(Some(tokens), Statement::parse_statement(line));
+        assert!(Statement::parse_statement("A = 0 -> B : x").is_none());
+        assert!(Statement::parse_statement("A = 0 -> B : 1 C").is_none());
+    }
+
+    #[test]
+    fn epsilon() {
+        let tokens = vec![
+            Operand("A".to_string()),
+            Operator("=".to_string()),
+            Operand("ε".to_string()),
+            Operator("->".to_string()),
+            Operand("B".to_string()),
+            Operand("C".to_string()),
+        ];
+        for line in ["    A = ε -> B C", "    A = eps -> B C"] {
+            let ad_tokens = Statement::parse_statement(line);
+            assert!(ad_tokens.is_some());
+            assert_eq!(tokens, ad_tokens.unwrap());
+        }
+        // only the symbols are spelled `eps`, states keep their name
+        let tokens = vec![
+            Operand("eps".to_string()),
+            Operator("=".to_string()),
+            Operand("0".to_string()),
+            Operator("->".to_string()),
+            Operand("eps".to_string()),
+        ];
+        assert_eq!(Some(tokens), Statement::parse_statement("eps = 0 -> eps"));
+    }
+
+    #[test]
+    fn classes() {
+        let line = "A = a-z \\d !0 -> B";
+        let tokens = vec![
+            Operand("A".to_string()),
+            Operator("=".to_string()),
+            Operand("a-z".to_string()),
+            Operand("\\d".to_string()),
+            Operand("!0".to_string()),
+            Operator("->".to_string()),
+            Operand("B".to_string()),
+        ];
+        assert_eq!(Some(tokens), Statement::parse_statement(line));
+        assert!(Statement::parse_statement("A = !_ -> B").is_none());
+        assert!(Statement::parse_statement("A = 0 -> a-z").is_none());
+    }
 }
@@ -0,0 +1,49 @@
+use super::{Statement, Token};
+
+/// Semirings the weights of a WFA block can be taken from.
+const SEMIRINGS: [&str; 3] = ["probability", "tropical", "counting"];
+
+impl Statement {
+    /// Weighted block, `WFA Name tropical {`.
+    pub fn wfadef_parse_statement(self, statement: &str) -> Option<Vec<Token>> {
+        let items: Vec<&str> = statement.trim().split_ascii_whitespace().collect();
+        let mut tokens = Vec::with_capacity(items.len());
+        for (i, item) in items.into_iter().enumerate() {
+            match i {
+                0 if item == "WFA" => tokens.push(Token::Operator(item.to_owned())),
+                1 if item.chars().all(char::is_alphabetic) => {
+                    tokens.push(Token::Operand(item.to_owned()))
+                }
+                2 if SEMIRINGS.contains(&item) => tokens.push(Token::Operator(item.to_owned())),
+                3 if item == "{" => tokens.push(Token::Operator(item.to_owned())),
+                4 if item == "//" => {
+                    tokens.push(Token::Operator(item.to_owned()));
+                    break;
+                }
+                _ => return None,
+            }
+        }
+        Some(tokens)
+    }
+}
+
+#[cfg(test)]
+mod wfadef_parse_statement_tests {
+    use super::{Statement, Token};
+
+    #[test]
+    fn basic() {
+        let line = "WFA Cheapest tropical { // cheapest path";
+        let tokens = vec![
+            Token::Operator("WFA".to_string()),
+            Token::Operand("Cheapest".to_string()),
+            Token::Operator("tropical".to_string()),
+            Token::Operator("{".to_string()),
+            Token::Operator("//".to_string()),
+        ];
+        let wfa_tokens = Statement::parse_statement(line);
+        assert!(wfa_tokens.is_some());
+        assert_eq!(tokens, wfa_tokens.unwrap());
+        assert!(Statement::parse_statement("WFA Cheapest cheap {").is_none());
+    }
+}